@@ -0,0 +1,256 @@
+// confirmed answers, keyed by (day, part, input file), kept in a plain text file:
+//
+// # day part input answer
+// 16 1 inputs/d16sample1.txt 7036
+//
+// answers never contain whitespace, so each line is split on whitespace
+
+use std::fs;
+use std::path::Path;
+
+use advent_2024_rust::days::Day;
+use advent_2024_rust::utils::Source;
+
+use crate::records;
+
+pub const ANSWERS_PATH: &str = "inputs/answers.txt";
+const LAYOUT: &str = "day part input answer";
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Expected {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub answer: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Outcome {
+    Pass,
+    Fail { expected: String, actual: String },
+    // the input couldn't be parsed
+    Error(String),
+    Missing(String),
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Check {
+    pub day: u32,
+    pub part: u32,
+    pub input: String,
+    pub outcome: Outcome,
+}
+
+fn parse_record(line: &str) -> Result<Expected, String> {
+    let fields = records::fields(line, LAYOUT)?;
+    let (day, part) = records::day_part(&fields, line)?;
+    Ok(Expected {
+        day,
+        part,
+        input: fields[2].to_string(),
+        answer: fields[3].to_string(),
+    })
+}
+
+pub fn load(path: &str) -> Result<Vec<Expected>, String> {
+    records::load(path, parse_record)
+}
+
+fn to_file_contents(answers: &Vec<Expected>) -> String {
+    let mut contents = format!("# {}\n", LAYOUT);
+    for a in answers {
+        contents.push_str(&format!("{} {} {} {}\n", a.day, a.part, a.input, a.answer));
+    }
+    contents
+}
+
+// add or replace the answer for (day, part, input), keeping the file sorted
+pub fn record(path: &str, expected: Expected) -> Result<(), String> {
+    if expected.answer.is_empty() || expected.answer.contains(char::is_whitespace) {
+        return Err(format!(
+            "answer '{}' must be a single word",
+            expected.answer
+        ));
+    }
+
+    let mut answers = load(path)?;
+    answers.retain(|a| (a.day, a.part, &a.input) != (expected.day, expected.part, &expected.input));
+    answers.push(expected);
+    answers.sort_by(|a, b| (a.day, &a.input, a.part).cmp(&(b.day, &b.input, b.part)));
+
+    fs::write(path, to_file_contents(&answers))
+        .map_err(|e| format!("could not write {}: {}", path, e))
+}
+
+// re-run every recorded answer for this day; a part with nothing recorded is reported as missing
+pub fn verify_day(day: &Day, answers: &Vec<Expected>) -> Vec<Check> {
+    let mut checks = vec![];
+    for part in [1, 2] {
+        let recorded: Vec<&Expected> = answers
+            .iter()
+            .filter(|a| a.day == day.day && a.part == part)
+            .collect();
+
+        if recorded.is_empty() {
+            checks.push(Check {
+                day: day.day,
+                part,
+                input: "-".to_string(),
+                outcome: Outcome::Missing("no recorded answer".to_string()),
+            });
+        }
+
+        for expected in recorded {
+            let outcome = if !Path::new(&expected.input).exists() {
+                Outcome::Missing("input file not found".to_string())
+            } else {
+                let solved = Source::read(&expected.input)
+                    .and_then(|input| day.solver.solve_part(&input, part));
+                match solved {
+                    Ok(actual) if actual.to_string() == expected.answer => Outcome::Pass,
+                    Ok(actual) => Outcome::Fail {
+                        expected: expected.answer.clone(),
+                        actual: actual.to_string(),
+                    },
+                    Err(e) => Outcome::Error(e.to_string()),
+                }
+            };
+            checks.push(Check {
+                day: day.day,
+                part,
+                input: expected.input.clone(),
+                outcome,
+            });
+        }
+    }
+    checks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_2024_rust::solution::{Answer, Solution};
+    use advent_2024_rust::testing::temp_path;
+    use advent_2024_rust::utils::ParseError;
+
+    #[test]
+    fn parse_test() {
+        let contents =
+            "# day part input answer\n\n1 2 inputs/d1.txt 31\n17 1 inputs/d17.txt 4,6,3\n";
+        assert_eq!(
+            records::parse(contents, parse_record),
+            Ok(vec![
+                Expected {
+                    day: 1,
+                    part: 2,
+                    input: "inputs/d1.txt".to_string(),
+                    answer: "31".to_string()
+                },
+                Expected {
+                    day: 17,
+                    part: 1,
+                    input: "inputs/d17.txt".to_string(),
+                    answer: "4,6,3".to_string()
+                }
+            ])
+        );
+
+        assert!(parse_record("1 2 inputs/d1.txt").is_err());
+        assert!(parse_record("x 2 inputs/d1.txt 31").is_err());
+        assert!(parse_record("1 2 inputs/d1.txt 31 32").is_err());
+    }
+
+    #[test]
+    fn record_test() {
+        let path = &temp_path("answers_record_test.txt");
+        let _ = fs::remove_file(path);
+
+        let expected = |day, part, answer: &str| Expected {
+            day,
+            part,
+            input: format!("inputs/d{}.txt", day),
+            answer: answer.to_string(),
+        };
+
+        record(path, expected(2, 1, "252")).unwrap();
+        record(path, expected(1, 1, "11")).unwrap();
+        // replaces the earlier answer rather than adding a second one
+        record(path, expected(2, 1, "253")).unwrap();
+        assert!(record(path, expected(2, 2, "has space")).is_err());
+
+        assert_eq!(
+            load(path),
+            Ok(vec![expected(1, 1, "11"), expected(2, 1, "253")])
+        );
+        fs::remove_file(path).unwrap();
+    }
+
+    struct PathLength;
+
+    impl Solution for PathLength {
+        type Input = usize;
+
+        fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+            Ok(input.name.len())
+        }
+
+        fn part1(&self, input: &Self::Input) -> Answer {
+            Answer::from(*input)
+        }
+
+        fn part2(&self, input: &Self::Input) -> Answer {
+            Answer::from(*input)
+        }
+    }
+
+    #[test]
+    fn verify_day_test() {
+        let day = Day {
+            day: 1,
+            solver: &PathLength,
+            note: None,
+        };
+        // "Cargo.toml" always exists when tests run and is 10 characters long
+        let answers = vec![
+            Expected {
+                day: 1,
+                part: 1,
+                input: "Cargo.toml".to_string(),
+                answer: "10".to_string(),
+            },
+            Expected {
+                day: 1,
+                part: 1,
+                input: "does/not/exist.txt".to_string(),
+                answer: "10".to_string(),
+            },
+            Expected {
+                day: 1,
+                part: 2,
+                input: "Cargo.toml".to_string(),
+                answer: "11".to_string(),
+            },
+        ];
+
+        let outcomes: Vec<Outcome> = verify_day(&day, &answers)
+            .into_iter()
+            .map(|c| c.outcome)
+            .collect();
+        assert_eq!(
+            outcomes,
+            vec![
+                Outcome::Pass,
+                Outcome::Missing("input file not found".to_string()),
+                Outcome::Fail {
+                    expected: "11".to_string(),
+                    actual: "10".to_string()
+                }
+            ]
+        );
+
+        assert_eq!(
+            verify_day(&day, &vec![])[0].outcome,
+            Outcome::Missing("no recorded answer".to_string())
+        );
+    }
+}
@@ -0,0 +1,270 @@
+// talking to the puzzle site. Settings come from advent.conf in the crate directory, one `key = value` per line:
+//
+// session = 53616c7465645f5f...   (the value of the site's session cookie)
+// base_url = https://adventofcode.com
+// year = 2024
+//
+// AOC_SESSION and AOC_BASE_URL in the environment take precedence over the file
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+pub const CONFIG_PATH: &str = "advent.conf";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "advent-2024-rust";
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Config {
+    pub session: Option<String>,
+    pub base_url: String,
+    pub year: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            session: None,
+            base_url: DEFAULT_BASE_URL.to_string(),
+            year: 2024,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum Fetched {
+    Downloaded,
+    AlreadyCached,
+}
+
+pub fn parse_config(contents: &str) -> Result<Config, String> {
+    let mut config = Config::default();
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(format!("line {}: expected 'key = value'", idx + 1))?;
+        let value = value.trim().to_string();
+        match key.trim() {
+            "session" => config.session = Some(value),
+            "base_url" => config.base_url = value,
+            "year" => {
+                config.year = value
+                    .parse()
+                    .map_err(|_| format!("line {}: invalid year '{}'", idx + 1, value))?
+            }
+            other => return Err(format!("line {}: unknown setting '{}'", idx + 1, other)),
+        }
+    }
+    Ok(config)
+}
+
+// the config file if there is one, then the environment on top
+pub fn load_config(path: &str) -> Result<Config, String> {
+    let mut config = if Path::new(path).exists() {
+        let contents =
+            fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+        parse_config(&contents).map_err(|e| format!("{}: {}", path, e))?
+    } else {
+        Config::default()
+    };
+    if let Ok(session) = env::var("AOC_SESSION") {
+        config.session = Some(session);
+    }
+    if let Ok(base_url) = env::var("AOC_BASE_URL") {
+        config.base_url = base_url;
+    }
+    Ok(config)
+}
+
+impl Config {
+    pub fn day_url(&self, day: u32) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url.trim_end_matches('/'),
+            self.year,
+            day
+        )
+    }
+
+    fn session_cookie(&self) -> Result<String, String> {
+        match &self.session {
+            Some(session) if !session.is_empty() => Ok(format!("session={}", session)),
+            _ => Err(format!(
+                "no session token: set AOC_SESSION or add 'session = ...' to {}",
+                CONFIG_PATH
+            )),
+        }
+    }
+}
+
+// the body of a successful response, or a readable error for anything else
+pub(crate) fn read_response(result: Result<ureq::Response, ureq::Error>) -> Result<String, String> {
+    match result {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| format!("could not read the response: {}", e)),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            let hint = if code == 400 || code == 500 {
+                " - is the session token still valid?"
+            } else {
+                ""
+            };
+            Err(format!("server returned {}{}: {}", code, hint, body.trim()))
+        }
+        Err(e) => Err(format!("request failed: {}", e)),
+    }
+}
+
+pub(crate) fn get(config: &Config, url: &str) -> Result<String, String> {
+    let request = ureq::get(url)
+        .set("Cookie", &config.session_cookie()?)
+        .set("User-Agent", USER_AGENT);
+    read_response(request.call())
+}
+
+pub(crate) fn post_form(
+    config: &Config,
+    url: &str,
+    form: &[(&str, &str)],
+) -> Result<String, String> {
+    let request = ureq::post(url)
+        .set("Cookie", &config.session_cookie()?)
+        .set("User-Agent", USER_AGENT);
+    read_response(request.send_form(form))
+}
+
+// download the day's input to dest, unless it is already there
+pub fn fetch_input(config: &Config, day: u32, dest: &str) -> Result<Fetched, String> {
+    if Path::new(dest).exists() {
+        return Ok(Fetched::AlreadyCached);
+    }
+    let body = get(config, &format!("{}/input", config.day_url(day)))?;
+    if body.is_empty() {
+        return Err(format!("the input for day {} came back empty", day));
+    }
+    fs::write(dest, body).map_err(|e| format!("could not write {}: {}", dest, e))?;
+    Ok(Fetched::Downloaded)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use advent_2024_rust::testing::temp_path;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread::{self, JoinHandle};
+
+    // a one-request-per-response HTTP server on a free local port, returning the requests it saw
+    pub(crate) fn stub_server(
+        responses: Vec<(u16, &'static str)>,
+    ) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut content = vec![0; content_length];
+                reader.read_exact(&mut content).unwrap();
+                request.push_str(&String::from_utf8(content).unwrap());
+                requests.push(request);
+
+                let response = format!(
+                    "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        (base_url, handle)
+    }
+
+    pub(crate) fn stub_config(base_url: &str) -> Config {
+        Config {
+            session: Some("secret".to_string()),
+            base_url: base_url.to_string(),
+            year: 2024,
+        }
+    }
+
+    #[test]
+    fn parse_config_test() {
+        let config = parse_config("# mine\nsession = abc123\nbase_url = http://localhost:8000/\n");
+        assert_eq!(
+            config,
+            Ok(Config {
+                session: Some("abc123".to_string()),
+                base_url: "http://localhost:8000/".to_string(),
+                year: 2024
+            })
+        );
+        assert_eq!(
+            config.unwrap().day_url(7),
+            "http://localhost:8000/2024/day/7"
+        );
+        assert!(parse_config("session abc").is_err());
+        assert!(parse_config("colour = red").is_err());
+        assert!(parse_config("year = soon").is_err());
+    }
+
+    #[test]
+    fn fetch_input_test() {
+        let dest = temp_path("aoc_fetch_input_test.txt");
+        let _ = fs::remove_file(&dest);
+        let (base_url, server) = stub_server(vec![(200, "1 2\n3 4\n")]);
+        let config = stub_config(&base_url);
+
+        assert_eq!(fetch_input(&config, 1, &dest), Ok(Fetched::Downloaded));
+        assert_eq!(fs::read_to_string(&dest).unwrap(), "1 2\n3 4\n");
+        // the stub only answers once, so a second download would hang rather than pass
+        assert_eq!(fetch_input(&config, 1, &dest), Ok(Fetched::AlreadyCached));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2024/day/1/input HTTP/1.1"));
+        assert!(requests[0].contains("session=secret"));
+        fs::remove_file(&dest).unwrap();
+    }
+
+    #[test]
+    fn fetch_input_errors_test() {
+        let dest = temp_path("aoc_fetch_input_errors_test.txt");
+        let _ = fs::remove_file(&dest);
+        let (base_url, server) = stub_server(vec![(400, "Puzzle inputs differ by user.")]);
+
+        let err = fetch_input(&stub_config(&base_url), 1, &dest).unwrap_err();
+        assert!(
+            err.contains("400 - is the session token still valid?"),
+            "{}",
+            err
+        );
+        assert!(!Path::new(&dest).exists());
+        server.join().unwrap();
+
+        let no_session = Config::default();
+        assert!(fetch_input(&no_session, 1, &dest)
+            .unwrap_err()
+            .contains("no session token"));
+    }
+}
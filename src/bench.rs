@@ -0,0 +1,187 @@
+// wall-clock timing of solvers, with results appended to a plain text file so runs can be compared:
+//
+// # unix_time day part runs min_us median_us max_us
+// 1733900000 6 2 5 4812331 4901220 5120004
+//
+// each timing covers parsing the input and solving the part, as `run` would
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use advent_2024_rust::days::Day;
+use advent_2024_rust::utils::{ParseError, Source};
+
+use crate::records;
+
+pub const BENCH_PATH: &str = "inputs/bench.txt";
+const LAYOUT: &str = "unix_time day part runs min_us median_us max_us";
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Timing {
+    pub day: u32,
+    pub part: u32,
+    pub runs: u32,
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+// the middle sample, or the mean of the two middle samples for an even count
+fn summarise(day: u32, part: u32, mut samples: Vec<Duration>) -> Timing {
+    samples.sort();
+    let n = samples.len();
+    let median = if n % 2 == 1 {
+        samples[n / 2]
+    } else {
+        (samples[n / 2 - 1] + samples[n / 2]) / 2
+    };
+    Timing {
+        day,
+        part,
+        runs: n as u32,
+        min: samples[0],
+        median,
+        max: samples[n - 1],
+    }
+}
+
+pub fn time_part(day: &Day, file_path: &str, part: u32, runs: u32) -> Result<Timing, ParseError> {
+    let samples = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            day.solver.solve_part(&Source::read(file_path)?, part)?;
+            Ok(start.elapsed())
+        })
+        .collect::<Result<_, _>>()?;
+    Ok(summarise(day.day, part, samples))
+}
+
+fn parse_record(line: &str) -> Result<(u64, Timing), String> {
+    let fields = records::fields(line, LAYOUT)?
+        .iter()
+        .map(|f| f.parse::<u64>())
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|_| format!("expected whole numbers, got '{}'", line))?;
+    Ok((
+        fields[0],
+        Timing {
+            day: fields[1] as u32,
+            part: fields[2] as u32,
+            runs: fields[3] as u32,
+            min: Duration::from_micros(fields[4]),
+            median: Duration::from_micros(fields[5]),
+            max: Duration::from_micros(fields[6]),
+        },
+    ))
+}
+
+// every saved timing, oldest first, along with when it was taken
+pub fn load(path: &str) -> Result<Vec<(u64, Timing)>, String> {
+    records::load(path, parse_record)
+}
+
+// the most recently saved timing for this day and part
+pub fn previous(saved: &Vec<(u64, Timing)>, day: u32, part: u32) -> Option<&Timing> {
+    saved
+        .iter()
+        .rev()
+        .map(|(_, t)| t)
+        .find(|t| t.day == day && t.part == part)
+}
+
+pub fn save(path: &str, timings: &Vec<Timing>) -> Result<(), String> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let is_new = !Path::new(path).exists();
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("could not open {}: {}", path, e))?;
+    let mut contents = String::new();
+    if is_new {
+        contents.push_str(&format!("# {}\n", LAYOUT));
+    }
+    for t in timings {
+        contents.push_str(&format!(
+            "{} {} {} {} {} {} {}\n",
+            now,
+            t.day,
+            t.part,
+            t.runs,
+            t.min.as_micros(),
+            t.median.as_micros(),
+            t.max.as_micros()
+        ));
+    }
+    file.write_all(contents.as_bytes())
+        .map_err(|e| format!("could not write {}: {}", path, e))
+}
+
+// e.g. "1.234s", "56.7ms", "890us"
+pub fn format_duration(d: Duration) -> String {
+    let micros = d.as_micros();
+    if micros >= 1_000_000 {
+        format!("{:.3}s", d.as_secs_f64())
+    } else if micros >= 1_000 {
+        format!("{:.1}ms", micros as f64 / 1_000.0)
+    } else {
+        format!("{}us", micros)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_2024_rust::testing::temp_path;
+    use std::fs;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn summarise_test() {
+        let timing = summarise(6, 2, vec![ms(30), ms(10), ms(20)]);
+        assert_eq!(
+            (timing.min, timing.median, timing.max),
+            (ms(10), ms(20), ms(30))
+        );
+        assert_eq!(timing.runs, 3);
+
+        let timing = summarise(6, 2, vec![ms(40), ms(10), ms(20), ms(30)]);
+        assert_eq!(timing.median, ms(25));
+    }
+
+    #[test]
+    fn save_load_test() {
+        let path = &temp_path("bench_save_load_test.txt");
+        let _ = fs::remove_file(path);
+
+        let first = summarise(7, 1, vec![ms(5), ms(7), ms(6)]);
+        let second = summarise(7, 1, vec![ms(3)]);
+        save(path, &vec![first.clone(), summarise(7, 2, vec![ms(9)])]).unwrap();
+        save(path, &vec![second.clone()]).unwrap();
+
+        let saved = load(path).unwrap();
+        assert_eq!(saved.len(), 3);
+        assert_eq!(saved[0].1, first);
+        assert_eq!(previous(&saved, 7, 1), Some(&second));
+        assert_eq!(previous(&saved, 8, 1), None);
+
+        assert!(parse_record("1 2 3").is_err());
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn format_duration_test() {
+        assert_eq!(format_duration(Duration::from_micros(890)), "890us");
+        assert_eq!(format_duration(Duration::from_micros(56_700)), "56.7ms");
+        assert_eq!(format_duration(Duration::from_micros(1_234_000)), "1.234s");
+    }
+}
@@ -0,0 +1,208 @@
+// what is known about an answer before it is right, kept per day and part in a plain text file:
+//
+// # day part kind value
+// 6 2 too_low 1792
+// 17 1 not 4,6,3
+//
+// too_low means the answer must be greater than value, too_high that it must be less,
+// and not that it is anything but value. Bounds only apply to the real input.
+
+use std::fs;
+
+use advent_2024_rust::solution::Answer;
+
+use crate::records;
+
+pub const BOUNDS_PATH: &str = "inputs/bounds.txt";
+const LAYOUT: &str = "day part kind value";
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Kind {
+    TooLow(i128),
+    TooHigh(i128),
+    Not(String),
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Bound {
+    pub day: u32,
+    pub part: u32,
+    pub kind: Kind,
+}
+
+impl Kind {
+    // too-low is accepted as well as too_low, since that is how flags are usually spelled
+    pub fn parse(kind: &str, value: &str) -> Result<Kind, String> {
+        let number = || {
+            value
+                .parse::<i128>()
+                .map_err(|_| format!("{} needs a whole number, got '{}'", kind, value))
+        };
+        match kind.replace('-', "_").as_str() {
+            "too_low" => Ok(Kind::TooLow(number()?)),
+            "too_high" => Ok(Kind::TooHigh(number()?)),
+            "not" => Ok(Kind::Not(value.to_string())),
+            _ => Err(format!(
+                "unknown bound '{}', expected too_low, too_high or not",
+                kind
+            )),
+        }
+    }
+
+    fn to_fields(&self) -> (&'static str, String) {
+        match self {
+            Kind::TooLow(n) => ("too_low", n.to_string()),
+            Kind::TooHigh(n) => ("too_high", n.to_string()),
+            Kind::Not(s) => ("not", s.clone()),
+        }
+    }
+
+    // why the answer can't be right, if it can't
+    fn violated_by(&self, answer: &str) -> Option<String> {
+        let number = answer.parse::<i128>().ok();
+        match (self, number) {
+            (Kind::TooLow(low), Some(n)) if n <= *low => {
+                Some(format!("{} was too low, so {} is too", low, n))
+            }
+            (Kind::TooHigh(high), Some(n)) if n >= *high => {
+                Some(format!("{} was too high, so {} is too", high, n))
+            }
+            (Kind::Not(wrong), _) if wrong == answer => {
+                Some(format!("{} is known to be wrong", wrong))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn parse_record(line: &str) -> Result<Bound, String> {
+    let fields = records::fields(line, LAYOUT)?;
+    let (day, part) = records::day_part(&fields, line)?;
+    Ok(Bound {
+        day,
+        part,
+        kind: Kind::parse(fields[2], fields[3])?,
+    })
+}
+
+pub fn load(path: &str) -> Result<Vec<Bound>, String> {
+    records::load(path, parse_record)
+}
+
+fn save(path: &str, bounds: &Vec<Bound>) -> Result<(), String> {
+    let mut contents = format!("# {}\n", LAYOUT);
+    for b in bounds {
+        let (kind, value) = b.kind.to_fields();
+        contents.push_str(&format!("{} {} {} {}\n", b.day, b.part, kind, value));
+    }
+    fs::write(path, contents).map_err(|e| format!("could not write {}: {}", path, e))
+}
+
+// add a bound, keeping the file sorted by day and part; returns false if it was already there
+pub fn add(path: &str, bound: Bound) -> Result<bool, String> {
+    let mut bounds = load(path)?;
+    if bounds.contains(&bound) {
+        return Ok(false);
+    }
+    bounds.push(bound);
+    bounds.sort_by_key(|b| (b.day, b.part));
+    save(path, &bounds)?;
+    Ok(true)
+}
+
+// remove every bound for the day and part, returning how many there were
+pub fn clear(path: &str, day: u32, part: u32) -> Result<usize, String> {
+    let mut bounds = load(path)?;
+    let before = bounds.len();
+    bounds.retain(|b| (b.day, b.part) != (day, part));
+    save(path, &bounds)?;
+    Ok(before - bounds.len())
+}
+
+// every reason the answer can't be right for this day and part
+pub fn violations(bounds: &Vec<Bound>, day: u32, part: u32, answer: &Answer) -> Vec<String> {
+    let answer = answer.to_string();
+    bounds
+        .iter()
+        .filter(|b| b.day == day && b.part == part)
+        .filter_map(|b| b.kind.violated_by(&answer))
+        .collect()
+}
+
+pub fn describe(bound: &Bound) -> String {
+    let condition = match &bound.kind {
+        Kind::TooLow(n) => format!("greater than {}", n),
+        Kind::TooHigh(n) => format!("less than {}", n),
+        Kind::Not(s) => format!("not {}", s),
+    };
+    format!("Day {:>2} Part {}: {}", bound.day, bound.part, condition)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_2024_rust::testing::temp_path;
+
+    fn bound(kind: Kind) -> Bound {
+        Bound {
+            day: 6,
+            part: 2,
+            kind,
+        }
+    }
+
+    #[test]
+    fn kind_parse_test() {
+        assert_eq!(Kind::parse("too_low", "1792"), Ok(Kind::TooLow(1792)));
+        assert_eq!(Kind::parse("too-high", "-5"), Ok(Kind::TooHigh(-5)));
+        assert_eq!(
+            Kind::parse("not", "4,6,3"),
+            Ok(Kind::Not("4,6,3".to_string()))
+        );
+        assert!(Kind::parse("too_low", "4,6,3").is_err());
+        assert!(Kind::parse("about", "5").is_err());
+    }
+
+    #[test]
+    fn violations_test() {
+        let bounds = vec![
+            bound(Kind::TooLow(1792)),
+            bound(Kind::TooHigh(2000)),
+            bound(Kind::Not("1933".to_string())),
+        ];
+        assert_eq!(violations(&bounds, 6, 2, &Answer::Int(1900)).len(), 0);
+        assert_eq!(
+            violations(&bounds, 6, 2, &Answer::Int(1792)),
+            vec!["1792 was too low, so 1792 is too".to_string()]
+        );
+        assert_eq!(violations(&bounds, 6, 2, &Answer::Int(2500)).len(), 1);
+        assert_eq!(
+            violations(&bounds, 6, 2, &Answer::Int(1933)),
+            vec!["1933 is known to be wrong".to_string()]
+        );
+        // other parts and answers that aren't numbers are only checked against 'not'
+        assert_eq!(violations(&bounds, 6, 1, &Answer::Int(5)).len(), 0);
+        assert_eq!(violations(&bounds, 6, 2, &Answer::from("1,2")).len(), 0);
+    }
+
+    #[test]
+    fn add_clear_test() {
+        let path = &temp_path("bounds_add_clear_test.txt");
+        let _ = fs::remove_file(path);
+
+        let low = bound(Kind::TooLow(1792));
+        let other_day = Bound {
+            day: 1,
+            part: 1,
+            kind: Kind::Not("5".to_string()),
+        };
+        assert_eq!(add(path, low.clone()), Ok(true));
+        assert_eq!(add(path, low.clone()), Ok(false));
+        assert_eq!(add(path, other_day.clone()), Ok(true));
+        assert_eq!(load(path), Ok(vec![other_day.clone(), low]));
+
+        assert_eq!(clear(path, 6, 2), Ok(1));
+        assert_eq!(load(path), Ok(vec![other_day]));
+        fs::remove_file(path).unwrap();
+    }
+}
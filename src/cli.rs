@@ -0,0 +1,778 @@
+// command line handling: parse the arguments into a Command, then run it
+
+use std::path::Path;
+use std::time::Instant;
+
+use crate::answers::{self, Expected, Outcome};
+use crate::aoc;
+use crate::bench;
+use crate::bounds::{self, Bound};
+use crate::inputs;
+use crate::parallel;
+use crate::scaffold;
+use crate::submit::{self, Verdict};
+use advent_2024_rust::days::{self, Day};
+use advent_2024_rust::solution::Answer;
+use advent_2024_rust::utils::{self, Source};
+
+pub const USAGE: &str = "usage:
+  advent-2024-rust list
+  advent-2024-rust run <day> [--part <1|2>] [--input <name or path>]
+  advent-2024-rust run <day> [--part <1|2>] --all-inputs
+  advent-2024-rust run --all [--jobs <n>]
+  advent-2024-rust verify [<day>]
+  advent-2024-rust bench [<day>] [--part <1|2>] [--runs <n>]
+  advent-2024-rust record <day> --part <1|2> [--input <name or path>] <answer>
+  advent-2024-rust new-day <day>
+  advent-2024-rust fetch <day>
+  advent-2024-rust submit <day> --part <1|2>
+  advent-2024-rust bounds [<day>]
+  advent-2024-rust bounds <day> --part <1|2> <too-low|too-high|not> <value>
+  advent-2024-rust bounds <day> --part <1|2> clear
+
+Any command also takes -q/--quiet, -v or -vv for less or more diagnostics on stderr,
+and --log-day <days> (e.g. --log-day 3,10) to only show diagnostics from those days.
+
+--input picks inputs/d<day><name>.txt, e.g. --input sample1 for inputs/d16sample1.txt.
+A value containing '/' or ending in .txt is used as a path as-is, and - reads standard input.
+Without --input, the real input inputs/d<day>.txt is used.
+--all-inputs runs against every inputs/d<day>*.txt in turn; list shows which ones exist.
+--jobs runs every part of every day on n threads at once, and prints a table once all are done.
+fetch downloads inputs/d<day>.txt unless it already exists, using the session token from
+AOC_SESSION or advent.conf ('session = ...', optionally 'base_url = ...' and 'year = ...').
+submit solves the part on the real input and posts the answer with the same settings, logging
+each verdict to inputs/guesses.txt; an answer already known to be wrong is never sent again.
+bounds are what earlier wrong answers said about the real answer, kept in inputs/bounds.txt.
+run flags real-input results that break them, submit refuses to send those, and adds a bound
+for each wrong answer it gets back.
+bench times each part on the real input (5 runs by default) and appends the results to inputs/bench.txt.";
+
+#[derive(PartialEq, Debug)]
+pub enum Command {
+    List,
+    Run {
+        day: u32,
+        part: Option<u32>,
+        input: Option<String>,
+    },
+    RunAllInputs {
+        day: u32,
+        part: Option<u32>,
+    },
+    RunAll {
+        jobs: Option<u32>,
+    },
+    Verify {
+        day: Option<u32>,
+    },
+    Record {
+        day: u32,
+        part: u32,
+        input: Option<String>,
+        answer: String,
+    },
+    Bench {
+        day: Option<u32>,
+        part: Option<u32>,
+        runs: u32,
+    },
+    NewDay {
+        day: u32,
+    },
+    Fetch {
+        day: u32,
+    },
+    Submit {
+        day: u32,
+        part: u32,
+    },
+    Bounds {
+        day: Option<u32>,
+    },
+    AddBound {
+        day: u32,
+        part: u32,
+        kind: bounds::Kind,
+    },
+    ClearBounds {
+        day: u32,
+        part: u32,
+    },
+}
+
+// the flags and positional arguments that follow a command
+#[derive(Default)]
+struct Options {
+    positional: Vec<String>,
+    part: Option<u32>,
+    input: Option<String>,
+    runs: Option<u32>,
+    jobs: Option<u32>,
+    all: bool,
+    all_inputs: bool,
+}
+
+fn parse_options<'a>(mut iter: impl Iterator<Item = &'a String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--all" => options.all = true,
+            "--all-inputs" => options.all_inputs = true,
+            "--part" | "-p" => {
+                let p = parse_number(arg, iter.next())?;
+                if p != 1 && p != 2 {
+                    return Err(format!("--part must be 1 or 2, got {}", p));
+                }
+                options.part = Some(p);
+            }
+            "--runs" | "-n" => {
+                let n = parse_number(arg, iter.next())?;
+                if n == 0 {
+                    return Err("--runs must be at least 1".to_string());
+                }
+                options.runs = Some(n);
+            }
+            "--jobs" | "-j" => {
+                let n = parse_number(arg, iter.next())?;
+                if n == 0 {
+                    return Err("--jobs must be at least 1".to_string());
+                }
+                options.jobs = Some(n);
+            }
+            "--input" | "-i" => {
+                let name = iter.next().ok_or("--input requires a value")?;
+                options.input = Some(name.clone());
+            }
+            // a leading '-' followed by a digit is a negative number, not a flag
+            flag if flag.starts_with('-')
+                && !flag[1..].starts_with(|c: char| c.is_ascii_digit()) =>
+            {
+                return Err(format!("unknown option '{}'", flag));
+            }
+            _ => options.positional.push(arg.clone()),
+        }
+    }
+    Ok(options)
+}
+
+fn parse_number(flag: &str, value: Option<&String>) -> Result<u32, String> {
+    let value = value.ok_or(format!("{} requires a value", flag))?;
+    value
+        .parse()
+        .map_err(|_| format!("{} expects a number, got '{}'", flag, value))
+}
+
+pub fn parse_args(args: &[String]) -> Result<Command, String> {
+    let mut iter = args.iter();
+    let command = iter.next().map(String::as_str);
+    if command == Some("list") {
+        return match iter.next() {
+            None => Ok(Command::List),
+            Some(arg) => Err(format!("unexpected argument '{}'", arg)),
+        };
+    }
+
+    let options = parse_options(iter)?;
+    let mut positional = options.positional.iter();
+    let day = positional
+        .next()
+        .map(|d| parse_number("day", Some(d)))
+        .transpose()?;
+
+    if options.runs.is_some() && command != Some("bench") {
+        return Err("--runs only applies to bench".to_string());
+    }
+    if options.jobs.is_some() && !(command == Some("run") && options.all) {
+        return Err("--jobs only applies to run --all".to_string());
+    }
+    if options.all_inputs && (command != Some("run") || options.input.is_some()) {
+        return Err("--all-inputs only applies to run, without --input".to_string());
+    }
+
+    let result = match command {
+        Some("run") => match (options.all, day) {
+            (true, None) if options.part.is_none() && options.input.is_none() => {
+                Ok(Command::RunAll { jobs: options.jobs })
+            }
+            (true, None) => Err("--all runs both parts on the real inputs".to_string()),
+            (true, Some(_)) => Err("give either a day or --all, not both".to_string()),
+            (false, None) => Err("run needs a day or --all".to_string()),
+            (false, Some(day)) if options.all_inputs => Ok(Command::RunAllInputs {
+                day,
+                part: options.part,
+            }),
+            (false, Some(day)) => Ok(Command::Run {
+                day,
+                part: options.part,
+                input: options.input.clone(),
+            }),
+        },
+        Some("verify") if options.part.is_none() && options.input.is_none() && !options.all => {
+            Ok(Command::Verify { day })
+        }
+        Some("verify") => Err("verify only takes an optional day".to_string()),
+        Some("record") => match (day, options.part, positional.next()) {
+            (Some(day), Some(part), Some(answer)) => Ok(Command::Record {
+                day,
+                part,
+                input: options.input.clone(),
+                answer: answer.clone(),
+            }),
+            _ => Err("record needs a day, --part and an answer".to_string()),
+        },
+        Some("bench") if options.input.is_none() && !options.all => Ok(Command::Bench {
+            day,
+            part: options.part,
+            runs: options.runs.unwrap_or(5),
+        }),
+        Some("bench") => {
+            Err("bench always uses the real inputs of every day, or the given day".to_string())
+        }
+        Some("new-day") => match day {
+            Some(day)
+                if options.part.is_none()
+                    && options.input.is_none()
+                    && !options.all
+                    && !options.all_inputs =>
+            {
+                Ok(Command::NewDay { day })
+            }
+            _ => Err("new-day needs just a day".to_string()),
+        },
+        Some("fetch") => match day {
+            Some(day)
+                if options.part.is_none()
+                    && options.input.is_none()
+                    && !options.all
+                    && !options.all_inputs =>
+            {
+                Ok(Command::Fetch { day })
+            }
+            _ => Err("fetch needs just a day".to_string()),
+        },
+        Some("submit") => match (day, options.part) {
+            (Some(day), Some(part))
+                if options.input.is_none() && !options.all && !options.all_inputs =>
+            {
+                Ok(Command::Submit { day, part })
+            }
+            _ => Err("submit needs a day and --part, and always uses the real input".to_string()),
+        },
+        Some("bounds") if options.input.is_some() || options.all || options.all_inputs => {
+            Err("bounds only apply to the real input".to_string())
+        }
+        Some("bounds") => match (day, options.part, positional.next()) {
+            (day, None, None) => Ok(Command::Bounds { day }),
+            (Some(day), Some(part), Some(kind)) if kind == "clear" => {
+                Ok(Command::ClearBounds { day, part })
+            }
+            (Some(day), Some(part), Some(kind)) => {
+                let value = positional.next().ok_or(format!("{} needs a value", kind))?;
+                Ok(Command::AddBound {
+                    day,
+                    part,
+                    kind: bounds::Kind::parse(kind, value)?,
+                })
+            }
+            _ => Err("bounds needs a day, --part and a bound to change".to_string()),
+        },
+        Some(other) => Err(format!("unknown command '{}'", other)),
+        None => Err("no command given".to_string()),
+    }?;
+
+    match positional.next() {
+        Some(arg) => Err(format!("unexpected argument '{}'", arg)),
+        None => Ok(result),
+    }
+}
+
+fn run_day(day: &Day, part: Option<u32>, path: &str) -> Result<(), String> {
+    let input = Source::read(path).map_err(|e| e.to_string())?;
+    let answers = match part {
+        Some(p) => vec![(
+            p,
+            day.solver
+                .solve_part(&input, p)
+                .map_err(|e| e.to_string())?,
+        )],
+        None => {
+            let (answer1, answer2) = day.solver.solve(&input).map_err(|e| e.to_string())?;
+            vec![(1, answer1), (2, answer2)]
+        }
+    };
+
+    // bounds were learned from the real input, so say nothing about samples
+    let known = if path == inputs::path(day.day, None) {
+        bounds::load(bounds::BOUNDS_PATH).unwrap_or_else(|e| {
+            log::warn!("not checking bounds: {}", e);
+            vec![]
+        })
+    } else {
+        vec![]
+    };
+    for (p, answer) in answers {
+        println!("Result Day {} Part {}: {}", day.day, p, answer);
+        for reason in bounds::violations(&known, day.day, p, &answer) {
+            println!("  impossible: {}", reason);
+        }
+    }
+    Ok(())
+}
+
+pub fn execute(command: Command) -> Result<(), String> {
+    match command {
+        Command::List => {
+            for day in days::registry() {
+                let found = inputs::discover(day.day);
+                let labels: Vec<&str> = found.iter().map(inputs::Input::label).collect();
+                let labels = if labels.is_empty() {
+                    "no inputs".to_string()
+                } else {
+                    labels.join(", ")
+                };
+                match day.note {
+                    Some(note) => println!("Day {:>2}  [{}]  ({})", day.day, labels, note),
+                    None => println!("Day {:>2}  [{}]", day.day, labels),
+                }
+            }
+        }
+        Command::Run { day, part, input } => {
+            let day = days::find(day).ok_or(format!("day {} is not implemented", day))?;
+            let path = inputs::resolve(day.day, input.as_deref())?;
+            run_day(&day, part, &path)?;
+        }
+        Command::RunAllInputs { day, part } => {
+            let day = days::find(day).ok_or(format!("day {} is not implemented", day))?;
+            let found = inputs::discover(day.day);
+            if found.is_empty() {
+                return Err(inputs::resolve(day.day, None).unwrap_err());
+            }
+            for input in found {
+                println!("== {} ({}) ==", input.label(), input.path);
+                // a malformed sample shouldn't hide the results for the others
+                if let Err(message) = run_day(&day, part, &input.path) {
+                    println!("error: {}", message);
+                }
+            }
+        }
+        Command::RunAll { jobs: Some(jobs) } => run_parallel(jobs as usize)?,
+        Command::RunAll { jobs: None } => {
+            for day in days::registry() {
+                match inputs::resolve(day.day, None) {
+                    Ok(path) => {
+                        if let Err(message) = run_day(&day, None, &path) {
+                            println!("error: {}", message);
+                        }
+                    }
+                    Err(message) => println!("Skipping Day {}: {}", day.day, message),
+                }
+            }
+        }
+        Command::Verify { day } => verify(day)?,
+        Command::Bench { day, part, runs } => run_bench(day, part, runs)?,
+        Command::NewDay { day } => {
+            for file in scaffold::new_day(Path::new("."), day)? {
+                println!("created {}", file);
+            }
+            println!("registered day {} in src/days.rs - rebuild to run it", day);
+        }
+        Command::Fetch { day } => {
+            let config = aoc::load_config(aoc::CONFIG_PATH)?;
+            let dest = inputs::path(day, None);
+            match aoc::fetch_input(&config, day, &dest)? {
+                aoc::Fetched::Downloaded => println!("saved {}", dest),
+                aoc::Fetched::AlreadyCached => println!("{} already exists, not downloading", dest),
+            }
+        }
+        Command::Submit { day, part } => {
+            let config = aoc::load_config(aoc::CONFIG_PATH)?;
+            let found = days::find(day).ok_or(format!("day {} is not implemented", day))?;
+            let path = inputs::resolve(day, None)?;
+            let answer = Source::read(&path)
+                .and_then(|input| found.solver.solve_part(&input, part))
+                .map_err(|e| e.to_string())?;
+            println!("Result Day {} Part {}: {}", day, part, answer);
+            if let Answer::Unsolved(_) = answer {
+                return Err("not submitting: there is no answer to submit".to_string());
+            }
+
+            let reasons =
+                bounds::violations(&bounds::load(bounds::BOUNDS_PATH)?, day, part, &answer);
+            if !reasons.is_empty() {
+                return Err(format!("not submitting: {}", reasons.join("; ")));
+            }
+
+            let answer = answer.to_string();
+            let verdict = submit::submit(&config, submit::GUESSES_PATH, day, part, &answer)?;
+            match &verdict {
+                Verdict::Correct => println!("correct!"),
+                Verdict::TooHigh => println!("wrong: too high"),
+                Verdict::TooLow => println!("wrong: too low"),
+                Verdict::Wrong => println!("wrong"),
+                Verdict::Wait(wait) => println!("submitted too soon: {} left to wait", wait),
+                Verdict::AlreadySolved => println!("this part has already been solved"),
+                Verdict::Unrecognised(text) => println!("unrecognised response: {}", text),
+            }
+
+            let kind = match verdict {
+                Verdict::TooHigh => bounds::Kind::parse("too_high", &answer).ok(),
+                Verdict::TooLow => bounds::Kind::parse("too_low", &answer).ok(),
+                Verdict::Wrong => Some(bounds::Kind::Not(answer)),
+                _ => None,
+            };
+            if let Some(kind) = kind {
+                bounds::add(bounds::BOUNDS_PATH, Bound { day, part, kind })?;
+            }
+        }
+        Command::Bounds { day } => {
+            let known = bounds::load(bounds::BOUNDS_PATH)?;
+            let shown: Vec<&Bound> = known
+                .iter()
+                .filter(|b| day.is_none_or(|d| b.day == d))
+                .collect();
+            if shown.is_empty() {
+                println!("no bounds recorded");
+            }
+            for bound in shown {
+                println!("{}", bounds::describe(bound));
+            }
+        }
+        Command::AddBound { day, part, kind } => {
+            let bound = Bound { day, part, kind };
+            let description = bounds::describe(&bound);
+            if bounds::add(bounds::BOUNDS_PATH, bound)? {
+                println!("added {}", description);
+            } else {
+                println!("already recorded: {}", description);
+            }
+        }
+        Command::ClearBounds { day, part } => {
+            let removed = bounds::clear(bounds::BOUNDS_PATH, day, part)?;
+            println!("removed {} bounds for day {} part {}", removed, day, part);
+        }
+        Command::Record {
+            day,
+            part,
+            input,
+            answer,
+        } => {
+            let input = inputs::resolve(day, input.as_deref())?;
+            if input == utils::STDIN {
+                return Err("record needs an input file to check against later".to_string());
+            }
+            answers::record(
+                answers::ANSWERS_PATH,
+                Expected {
+                    day,
+                    part,
+                    input,
+                    answer,
+                },
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn run_parallel(threads: usize) -> Result<(), String> {
+    let mut jobs = vec![];
+    for day in days::registry() {
+        match inputs::resolve(day.day, None) {
+            Ok(path) => {
+                for part in [1, 2] {
+                    jobs.push(parallel::Job {
+                        day: day.clone(),
+                        part,
+                        path: path.clone(),
+                    });
+                }
+            }
+            Err(message) => println!("Skipping Day {}: {}", day.day, message),
+        }
+    }
+
+    let start = Instant::now();
+    let reports = parallel::run(&jobs, threads);
+    for line in parallel::format_table(&reports) {
+        println!("{}", line);
+    }
+    let failed = reports.iter().filter(|r| r.result.is_err()).count();
+    println!(
+        "{} parts in {} on {} threads, {} failed",
+        reports.len(),
+        bench::format_duration(start.elapsed()),
+        threads,
+        failed
+    );
+
+    if failed > 0 {
+        Err(format!("{} parts failed", failed))
+    } else {
+        Ok(())
+    }
+}
+
+fn verify(day: Option<u32>) -> Result<(), String> {
+    let to_verify = match day {
+        Some(d) => vec![days::find(d).ok_or(format!("day {} is not implemented", d))?],
+        None => days::registry(),
+    };
+    let answers = answers::load(answers::ANSWERS_PATH)?;
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    for day in to_verify {
+        for check in answers::verify_day(&day, &answers) {
+            let status = match &check.outcome {
+                Outcome::Pass => {
+                    passed += 1;
+                    "pass".to_string()
+                }
+                Outcome::Fail { expected, actual } => {
+                    failed += 1;
+                    format!("FAIL (expected {}, got {})", expected, actual)
+                }
+                Outcome::Error(message) => {
+                    failed += 1;
+                    format!("ERROR ({})", message)
+                }
+                Outcome::Missing(reason) => {
+                    missing += 1;
+                    format!("missing ({})", reason)
+                }
+            };
+            println!(
+                "Day {:>2} Part {} {:<24} {}",
+                check.day, check.part, check.input, status
+            );
+        }
+    }
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    if failed > 0 {
+        Err(format!("{} answers did not match", failed))
+    } else {
+        Ok(())
+    }
+}
+
+fn run_bench(day: Option<u32>, part: Option<u32>, runs: u32) -> Result<(), String> {
+    let to_bench = match day {
+        Some(d) => vec![days::find(d).ok_or(format!("day {} is not implemented", d))?],
+        None => days::registry(),
+    };
+    let parts = match part {
+        Some(p) => vec![p],
+        None => vec![1, 2],
+    };
+    let saved = bench::load(bench::BENCH_PATH)?;
+
+    let mut timings = vec![];
+    for day in to_bench {
+        let path = match inputs::resolve(day.day, None) {
+            Ok(path) => path,
+            Err(message) => {
+                println!("Skipping Day {}: {}", day.day, message);
+                continue;
+            }
+        };
+        for &p in &parts {
+            timings.push(bench::time_part(&day, &path, p, runs).map_err(|e| e.to_string())?);
+        }
+    }
+
+    println!("Day Part  Runs        Min     Median        Max  Previous median");
+    for t in &timings {
+        let previous = bench::previous(&saved, t.day, t.part)
+            .map(|prev| bench::format_duration(prev.median))
+            .unwrap_or("-".to_string());
+        println!(
+            "{:>3} {:>4} {:>5} {:>10} {:>10} {:>10}  {}",
+            t.day,
+            t.part,
+            t.runs,
+            bench::format_duration(t.min),
+            bench::format_duration(t.median),
+            bench::format_duration(t.max),
+            previous
+        );
+    }
+    bench::save(bench::BENCH_PATH, &timings)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn parse_run_test() {
+        assert_eq!(
+            parse_args(&args("run 16 --part 2 --input sample1")),
+            Ok(Command::Run {
+                day: 16,
+                part: Some(2),
+                input: Some("sample1".to_string())
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run 3")),
+            Ok(Command::Run {
+                day: 3,
+                part: None,
+                input: None
+            })
+        );
+        assert_eq!(
+            parse_args(&args("run --all")),
+            Ok(Command::RunAll { jobs: None })
+        );
+        assert_eq!(
+            parse_args(&args("run --all -j 4")),
+            Ok(Command::RunAll { jobs: Some(4) })
+        );
+        assert!(parse_args(&args("run --all --jobs 0")).is_err());
+        assert!(parse_args(&args("run 6 --jobs 4")).is_err());
+        assert_eq!(
+            parse_args(&args("run 16 --all-inputs -p 1")),
+            Ok(Command::RunAllInputs {
+                day: 16,
+                part: Some(1)
+            })
+        );
+        assert_eq!(parse_args(&args("list")), Ok(Command::List));
+    }
+
+    #[test]
+    fn parse_verify_record_test() {
+        assert_eq!(
+            parse_args(&args("verify")),
+            Ok(Command::Verify { day: None })
+        );
+        assert_eq!(
+            parse_args(&args("verify 7")),
+            Ok(Command::Verify { day: Some(7) })
+        );
+        assert_eq!(
+            parse_args(&args("record 17 --part 1 4,6,3")),
+            Ok(Command::Record {
+                day: 17,
+                part: 1,
+                input: None,
+                answer: "4,6,3".to_string()
+            })
+        );
+        // negative answers are not mistaken for flags
+        assert_eq!(
+            parse_args(&args("record 14 -p 2 -i sample -2487")),
+            Ok(Command::Record {
+                day: 14,
+                part: 2,
+                input: Some("sample".to_string()),
+                answer: "-2487".to_string()
+            })
+        );
+        assert!(parse_args(&args("verify 7 --part 1")).is_err());
+        assert!(parse_args(&args("record 17 4,6,3")).is_err());
+        assert!(parse_args(&args("record 17 --part 1")).is_err());
+    }
+
+    #[test]
+    fn parse_bench_test() {
+        assert_eq!(
+            parse_args(&args("bench")),
+            Ok(Command::Bench {
+                day: None,
+                part: None,
+                runs: 5
+            })
+        );
+        assert_eq!(
+            parse_args(&args("bench 6 --part 2 --runs 3")),
+            Ok(Command::Bench {
+                day: Some(6),
+                part: Some(2),
+                runs: 3
+            })
+        );
+        assert!(parse_args(&args("bench 6 --runs 0")).is_err());
+        assert!(parse_args(&args("bench 6 --input sample")).is_err());
+        assert!(parse_args(&args("run 6 --runs 3")).is_err());
+    }
+
+    #[test]
+    fn parse_new_day_test() {
+        assert_eq!(
+            parse_args(&args("new-day 18")),
+            Ok(Command::NewDay { day: 18 })
+        );
+        assert!(parse_args(&args("new-day")).is_err());
+        assert!(parse_args(&args("new-day 18 --part 1")).is_err());
+        assert!(parse_args(&args("new-day 18 19")).is_err());
+        assert_eq!(
+            parse_args(&args("fetch 18")),
+            Ok(Command::Fetch { day: 18 })
+        );
+        assert!(parse_args(&args("fetch 18 --input sample")).is_err());
+        assert_eq!(
+            parse_args(&args("submit 6 --part 2")),
+            Ok(Command::Submit { day: 6, part: 2 })
+        );
+        assert!(parse_args(&args("submit 6")).is_err());
+        assert!(parse_args(&args("submit 6 -p 2 -i sample")).is_err());
+    }
+
+    #[test]
+    fn parse_bounds_test() {
+        assert_eq!(
+            parse_args(&args("bounds")),
+            Ok(Command::Bounds { day: None })
+        );
+        assert_eq!(
+            parse_args(&args("bounds 6")),
+            Ok(Command::Bounds { day: Some(6) })
+        );
+        assert_eq!(
+            parse_args(&args("bounds 6 --part 2 too-low 1792")),
+            Ok(Command::AddBound {
+                day: 6,
+                part: 2,
+                kind: bounds::Kind::TooLow(1792)
+            })
+        );
+        assert_eq!(
+            parse_args(&args("bounds 14 -p 2 not -2487")),
+            Ok(Command::AddBound {
+                day: 14,
+                part: 2,
+                kind: bounds::Kind::Not("-2487".to_string())
+            })
+        );
+        assert_eq!(
+            parse_args(&args("bounds 6 -p 2 clear")),
+            Ok(Command::ClearBounds { day: 6, part: 2 })
+        );
+        assert!(parse_args(&args("bounds 6 -p 2")).is_err());
+        assert!(parse_args(&args("bounds 6 -p 2 too-low")).is_err());
+        assert!(parse_args(&args("bounds 6 -p 2 too-low many")).is_err());
+        assert!(parse_args(&args("bounds 6 -p 2 too-low 5 6")).is_err());
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        assert!(parse_args(&args("")).is_err());
+        assert!(parse_args(&args("jump 3")).is_err());
+        assert!(parse_args(&args("run")).is_err());
+        assert!(parse_args(&args("run x")).is_err());
+        assert!(parse_args(&args("run 3 4")).is_err());
+        assert!(parse_args(&args("run 3 --part 3")).is_err());
+        assert!(parse_args(&args("run 3 --part")).is_err());
+        assert!(parse_args(&args("run 3 --all")).is_err());
+        assert!(parse_args(&args("run 3 --verbose")).is_err());
+        assert!(parse_args(&args("list 3")).is_err());
+        assert!(parse_args(&args("run 3 --all-inputs --input sample")).is_err());
+        assert!(parse_args(&args("verify 3 --all-inputs")).is_err());
+    }
+}
@@ -1,3 +1,6 @@
+//! One module per day, each with a `DayN` implementing [`Solution`](crate::solution::Solution),
+//! and a registry of them all.
+
 pub mod d1;
 pub mod d10;
 pub mod d11;
@@ -19,3 +22,147 @@ pub mod d6;
 pub mod d7;
 pub mod d8;
 pub mod d9;
+
+use crate::solution::Solver;
+
+/// A day in the registry, with its solver behind [`Solver`] so that every day has the same type.
+#[derive(Clone)]
+pub struct Day {
+    pub day: u32,
+    pub solver: &'static dyn Solver,
+    // a reminder shown when listing days, e.g. that a part is slow
+    pub note: Option<&'static str>,
+}
+
+/// Every implemented day, in calendar order.
+pub fn registry() -> Vec<Day> {
+    vec![
+        Day {
+            day: 1,
+            solver: &d1::Day1,
+            note: None,
+        },
+        Day {
+            day: 2,
+            solver: &d2::Day2,
+            note: None,
+        },
+        Day {
+            day: 3,
+            solver: &d3::Day3,
+            note: None,
+        },
+        Day {
+            day: 4,
+            solver: &d4::Day4,
+            note: None,
+        },
+        Day {
+            day: 5,
+            solver: &d5::Day5,
+            note: None,
+        },
+        Day {
+            day: 6,
+            solver: &d6::Day6,
+            note: Some("slow - can I improve it?"),
+        },
+        Day {
+            day: 7,
+            solver: &d7::Day7,
+            note: Some("slow - can I improve it?"),
+        },
+        Day {
+            day: 8,
+            solver: &d8::Day8,
+            note: None,
+        },
+        Day {
+            day: 9,
+            solver: &d9::Day9,
+            note: None,
+        },
+        Day {
+            day: 10,
+            solver: &d10::Day10,
+            note: None,
+        },
+        Day {
+            day: 11,
+            solver: &d11::Day11,
+            note: None,
+        },
+        Day {
+            day: 12,
+            solver: &d12::Day12,
+            note: None,
+        },
+        Day {
+            day: 13,
+            solver: &d13::Day13,
+            note: None,
+        },
+        Day {
+            day: 14,
+            solver: &d14::Day14,
+            note: None,
+        },
+        Day {
+            day: 15,
+            solver: &d15::Day15,
+            note: None,
+        },
+        Day {
+            day: 16,
+            solver: &d16::Day16,
+            note: None,
+        },
+        Day {
+            day: 17,
+            solver: &d17::Day17,
+            note: None,
+        },
+        Day {
+            day: 18,
+            solver: &d18::Day18,
+            note: None,
+        },
+        Day {
+            day: 19,
+            solver: &d19::Day19,
+            note: None,
+        },
+        Day {
+            day: 20,
+            solver: &d20::Day20,
+            note: None,
+        },
+        Day {
+            day: 21,
+            solver: &d21::Day21,
+            note: None,
+        },
+    ]
+}
+
+/// The registry entry for a day, if it has been implemented.
+pub fn find(day: u32) -> Option<Day> {
+    registry().into_iter().find(|d| d.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_in_order_test() {
+        let days: Vec<u32> = registry().iter().map(|d| d.day).collect();
+        assert!(days.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn find_test() {
+        assert_eq!(find(1).map(|d| d.day), Some(1));
+        assert!(find(26).is_none());
+    }
+}
@@ -1,6 +1,8 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
 use crate::utils::matrix;
 use crate::utils::plane;
+use crate::utils::{self, ParseError, Source};
+use log::{debug, trace};
 
 fn one_step(
     plane: &Vec<Vec<u32>>,
@@ -36,14 +38,13 @@ fn reachable_destinations(
     start_row: usize,
     start_col: usize,
 ) -> Vec<(usize, usize)> {
-    println!();
-    println!("starting at n=0: {}, {}", start_row, start_col);
+    debug!("starting at n=0: {}, {}", start_row, start_col);
     let mut n = 0;
     let mut all_n = vec![(start_row, start_col)];
     while n < destination {
         n += 1;
         all_n = one_step(plane, n, all_n);
-        println!("all n={}: {:?}", n, all_n);
+        trace!("all n={}: {:?}", n, all_n);
     }
     all_n
 }
@@ -55,7 +56,7 @@ pub fn paths_forward(
     start_col: usize,
 ) -> Vec<Vec<(usize, usize)>> {
     let curr_val = plane[start_row][start_col];
-    println!("{} @ ({},{})", curr_val, start_row, start_col);
+    trace!("{} @ ({},{})", curr_val, start_row, start_col);
 
     [
         plane::Direction::Down,
@@ -69,7 +70,7 @@ pub fn paths_forward(
         if !mo.out_of_bounds && plane[mo.row][mo.col] == curr_val + 1 {
             if plane[mo.row][mo.col] == target {
                 let last_step = vec![vec![(start_row, start_col), (mo.row, mo.col)]];
-                println!("last_step: {:?}", last_step);
+                trace!("last_step: {:?}", last_step);
                 last_step
             } else {
                 paths_forward(plane, target, mo.row, mo.col)
@@ -95,9 +96,8 @@ fn p1(plane: &Vec<Vec<u32>>, destination: u32) -> usize {
         .sum()
 }
 
-pub fn d10p1(file_path: &str) -> usize {
-    let mat = matrix::as_digit_matrix(file_path, 10);
-    p1(&mat, 9)
+pub fn d10p1(mat: &Vec<Vec<u32>>) -> usize {
+    p1(mat, 9)
 }
 
 fn p2(plane: &Vec<Vec<u32>>, destination: u32) -> usize {
@@ -107,117 +107,93 @@ fn p2(plane: &Vec<Vec<u32>>, destination: u32) -> usize {
         .sum()
 }
 
-pub fn d10p2(file_path: &str) -> usize {
-    let mat = matrix::as_digit_matrix(file_path, 10);
-    p2(&mat, 9)
+pub fn d10p2(mat: &Vec<Vec<u32>>) -> usize {
+    p2(mat, 9)
 }
 
-pub fn d10() {
-    //    let file_path = "inputs/d10sample1.txt";
-    //    let file_path = "inputs/d10sample2.txt";
-    let file_path = "inputs/d10.txt";
-    let mut result = d10p1(file_path);
-    println!("Result Day 10 Part 1: {}", result);
-    result = d10p2(file_path);
-    println!("Result Day 10 Part 2: {}", result);
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        Ok(matrix::as_digit_matrix_from_str(&input.text, 10))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d10p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d10p2(input).into()
+    }
 }
 
+#[cfg(test)]
 mod tests {
     use super::*;
-
-    fn as_int_matrix(plane: Vec<String>) -> Vec<Vec<u32>> {
-        plane.iter().map(|s| matrix::to_digit_vec(s, 10)).collect()
-    }
+    use crate::solution::Solver;
+
+    const EXAMPLE: &str = "\
+89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+";
 
     #[test]
     fn test_p1() {
-        let mut mat = as_int_matrix(vec![
-            "0123".to_string(),
-            "1234".to_string(),
-            "8765".to_string(),
-            "9876".to_string(),
-        ]);
+        let mut mat = matrix::as_digit_matrix_from_str("0123\n1234\n8765\n9876", 10);
         assert_eq!(p1(&mat, 9), 1);
 
-        mat = as_int_matrix(vec![
-            "...0...".to_string(),
-            "...1...".to_string(),
-            "...2...".to_string(),
-            "6543456".to_string(),
-            "7.....7".to_string(),
-            "8.....8".to_string(),
-            "9.....9".to_string(),
-        ]);
+        mat = matrix::as_digit_matrix_from_str(
+            "...0...\n...1...\n...2...\n6543456\n7.....7\n8.....8\n9.....9",
+            10,
+        );
         assert_eq!(p1(&mat, 9), 2);
 
-        mat = as_int_matrix(vec![
-            "10..9..".to_string(),
-            "2...8..".to_string(),
-            "3...7..".to_string(),
-            "4567654".to_string(),
-            "...8..3".to_string(),
-            "...9..2".to_string(),
-            ".....01".to_string(),
-        ]);
+        mat = matrix::as_digit_matrix_from_str(
+            "10..9..\n2...8..\n3...7..\n4567654\n...8..3\n...9..2\n.....01",
+            10,
+        );
         assert_eq!(p1(&mat, 9), 3);
 
-        mat = as_int_matrix(vec![
-            "89010123".to_string(),
-            "78121874".to_string(),
-            "87430965".to_string(),
-            "96549874".to_string(),
-            "45678903".to_string(),
-            "32019012".to_string(),
-            "01329801".to_string(),
-            "10456732".to_string(),
-        ]);
+        mat = matrix::as_digit_matrix_from_str(EXAMPLE, 10);
         assert_eq!(p1(&mat, 9), 36);
     }
 
     #[test]
     fn test_p2() {
-        let mut mat = as_int_matrix(vec![
-            ".....0.".to_string(),
-            "..4321.".to_string(),
-            "..5..2.".to_string(),
-            "..6543.".to_string(),
-            "..7..4.".to_string(),
-            "..8765.".to_string(),
-            "..9....".to_string(),
-        ]);
+        let mut mat = matrix::as_digit_matrix_from_str(
+            ".....0.\n..4321.\n..5..2.\n..6543.\n..7..4.\n..8765.\n..9....",
+            10,
+        );
         assert_eq!(p2(&mat, 9), 3);
 
-        mat = as_int_matrix(vec![
-            "..90..9".to_string(),
-            "...1.98".to_string(),
-            "...2..7".to_string(),
-            "6543456".to_string(),
-            "765.987".to_string(),
-            "876....".to_string(),
-            "987....".to_string(),
-        ]);
+        mat = matrix::as_digit_matrix_from_str(
+            "..90..9\n...1.98\n...2..7\n6543456\n765.987\n876....\n987....",
+            10,
+        );
         assert_eq!(p2(&mat, 9), 13);
 
-        mat = as_int_matrix(vec![
-            "012345".to_string(),
-            "123456".to_string(),
-            "234567".to_string(),
-            "345678".to_string(),
-            "4.6789".to_string(),
-            "56789.".to_string(),
-        ]);
+        mat =
+            matrix::as_digit_matrix_from_str("012345\n123456\n234567\n345678\n4.6789\n56789.", 10);
         assert_eq!(p2(&mat, 9), 227);
 
-        mat = as_int_matrix(vec![
-            "89010123".to_string(),
-            "78121874".to_string(),
-            "87430965".to_string(),
-            "96549874".to_string(),
-            "45678903".to_string(),
-            "32019012".to_string(),
-            "01329801".to_string(),
-            "10456732".to_string(),
-        ]);
+        mat = matrix::as_digit_matrix_from_str(EXAMPLE, 10);
         assert_eq!(p2(&mat, 9), 81);
     }
+
+    #[test]
+    fn solve_example_test() {
+        let solver: &dyn Solver = &Day10;
+        assert_eq!(
+            solver.solve(&Source::new("example", EXAMPLE)),
+            Ok((Answer::Int(36), Answer::Int(81)))
+        );
+    }
 }
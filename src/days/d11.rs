@@ -1,11 +1,12 @@
-use crate::utils;
-use core::panic;
+use crate::solution::{Answer, Solution};
+use crate::utils::{self, ParseError, Source};
+use log::trace;
 use std::collections::HashMap;
 
 fn rules(n: &u64) -> Vec<u64> {
     let result = match n {
         0 => vec![1],
-        even_digits if utils::digit_count(*n) % 2 == 0 => {
+        even_digits if utils::digit_count(*n).is_multiple_of(2) => {
             let digits_per_half = utils::digit_count(*n) / 2;
             let divider = 10_u64.pow(digits_per_half);
             vec![even_digits / divider, even_digits % divider]
@@ -13,7 +14,7 @@ fn rules(n: &u64) -> Vec<u64> {
         other => vec![other * 2024],
     };
 
-    // println!("{:?} -> {:?}", n, result);
+    trace!("{:?} -> {:?}", n, result);
     result
 }
 
@@ -40,28 +41,40 @@ fn apply_n_rules_h(h: HashMap<u64, u64>, n: usize) -> HashMap<u64, u64> {
     iterator
 }
 
+// the input is a single line of stones; count how many of each stone there are
+fn parse(input: &Source) -> Result<HashMap<u64, u64>, ParseError> {
+    match utils::as_spaced_int_vec_from_source::<u64>(input)?
+        .into_iter()
+        .next()
+    {
+        Some(stones) => Ok(utils::freqs_u64(stones)),
+        None => Err(input.error("expected a line of stones")),
+    }
+}
+
 pub fn d11p1(h: &HashMap<u64, u64>) -> u64 {
-    apply_n_rules_h(h.clone(), 25)
-        .iter()
-        .map(|(_, count)| count)
-        .sum()
+    apply_n_rules_h(h.clone(), 25).values().sum()
 }
 
-// ok to move/consume h here
-pub fn d11p2(h: HashMap<u64, u64>) -> u64 {
-    apply_n_rules_h(h, 75).iter().map(|(_, count)| count).sum()
+pub fn d11p2(h: &HashMap<u64, u64>) -> u64 {
+    apply_n_rules_h(h.clone(), 75).values().sum()
 }
 
-pub fn d11() {
-    let file_path = "inputs/d11.txt";
-    if let Some(stones) = utils::as_spaced_int_vec::<u64>(file_path).next() {
-        let h = utils::freqs_u64(stones);
-        let mut result = d11p1(&h);
-        println!("Result Day 11 Part 1: {}", result);
-        result = d11p2(h);
-        println!("Result Day 11 Part 2: {}", result);
-    } else {
-        panic!("Could not parse input");
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = HashMap<u64, u64>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d11p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d11p2(input).into()
     }
 }
 
@@ -99,19 +112,7 @@ mod tests {
     fn test_apply_n_rules_h() {
         let h = HashMap::from([(125, 1), (17, 1)]);
         let h2 = h.clone();
-        assert_eq!(
-            apply_n_rules_h(h, 6)
-                .iter()
-                .map(|(_, count)| count)
-                .sum::<u64>(),
-            22
-        );
-        assert_eq!(
-            apply_n_rules_h(h2, 25)
-                .iter()
-                .map(|(_, count)| count)
-                .sum::<u64>(),
-            55312
-        );
+        assert_eq!(apply_n_rules_h(h, 6).values().sum::<u64>(), 22);
+        assert_eq!(apply_n_rules_h(h2, 25).values().sum::<u64>(), 55312);
     }
 }
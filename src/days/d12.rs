@@ -1,3 +1,4 @@
+use crate::solution::{Answer, Solution};
 // idea: store 3 planes with identical coordinates and a Vec
 // plane 1 is the input plots
 // plane 2 is which region the plot is in
@@ -11,6 +12,8 @@
 
 use crate::utils::matrix;
 use crate::utils::plane::{self, Direction, MovingObject};
+use crate::utils::{ParseError, Source};
+use log::trace;
 
 fn same_region(plots: &Vec<Vec<char>>, plot_char: char, other_plot_maybe: &MovingObject) -> bool {
     !other_plot_maybe.out_of_bounds
@@ -66,10 +69,15 @@ fn edge_contribution(plots: &Vec<Vec<char>>, row_idx: usize, col_idx: usize) ->
     let left_has_down_edge =
         left_in_region && has_edge(plots, left.row, left.col, &Direction::Down);
 
-    // println!(
-    //     "up_left, up_right, left_up, left_down: ({},{}) -> {} {} {} {}",
-    //     row_idx, col_idx, up_has_left_edge, up_has_right_edge, left_has_up_edge, left_has_down_edge
-    // );
+    trace!(
+        "up_left, up_right, left_up, left_down: ({},{}) -> {} {} {} {}",
+        row_idx,
+        col_idx,
+        up_has_left_edge,
+        up_has_right_edge,
+        left_has_up_edge,
+        left_has_down_edge
+    );
 
     let mut edge_count: usize = 0;
     if !up_has_left_edge && has_edge(plots, row_idx, col_idx, &Direction::Left) {
@@ -106,12 +114,12 @@ fn merge_regions(
     edges_of_region[region_to_remove] = 0;
 
     let total_cols = region_of_plot[0].len();
-    for r in 0..=row_idx {
+    for (r, row) in region_of_plot.iter_mut().enumerate().take(row_idx + 1) {
         // only iterate up to col_idx for the last row
         let col_count = if r == row_idx { col_idx } else { total_cols };
-        for c in 0..col_count {
-            if region_of_plot[r][c] == region_to_remove {
-                region_of_plot[r][c] = merged_region;
+        for region in row.iter_mut().take(col_count) {
+            if *region == region_to_remove {
+                *region = merged_region;
             }
         }
     }
@@ -172,11 +180,11 @@ fn parse(plots: &Vec<Vec<char>>) -> (Vec<Vec<usize>>, Vec<usize>, Vec<usize>) {
         region_of_plot.push(Vec::with_capacity(col_count));
 
         for (col_idx, plot_char) in row.iter().enumerate() {
-            let plot_perimeter = perimeter_at(&plots, row_idx, col_idx);
-            let plot_edges = edge_contribution(&plots, row_idx, col_idx);
+            let plot_perimeter = perimeter_at(plots, row_idx, col_idx);
+            let plot_edges = edge_contribution(plots, row_idx, col_idx);
 
             let plot_region = continues_region(
-                &plots,
+                plots,
                 &mut region_of_plot,
                 &mut perimeter_of_region,
                 &mut edges_of_region,
@@ -216,7 +224,7 @@ fn parse(plots: &Vec<Vec<char>>) -> (Vec<Vec<usize>>, Vec<usize>, Vec<usize>) {
 fn p1(plots: &Vec<Vec<char>>) -> usize {
     let (row_count, col_count) = (plots.len(), plots[0].len());
 
-    let (region_of_plot, perimeter_of_region, _) = parse(&plots);
+    let (region_of_plot, perimeter_of_region, _) = parse(plots);
 
     (0..row_count)
         .map(|row_idx| {
@@ -233,7 +241,7 @@ fn p1(plots: &Vec<Vec<char>>) -> usize {
 fn p2(plots: &Vec<Vec<char>>) -> usize {
     let (row_count, col_count) = (plots.len(), plots[0].len());
 
-    let (region_of_plot, _, edges_of_region) = parse(&plots);
+    let (region_of_plot, _, edges_of_region) = parse(plots);
 
     (0..row_count)
         .map(|row_idx| {
@@ -241,7 +249,7 @@ fn p2(plots: &Vec<Vec<char>>) -> usize {
                 .map(|col_idx| {
                     let region = region_of_plot[row_idx][col_idx];
                     let result = edges_of_region[region];
-                    // println!("region {} -> {} edges", region, result);
+                    trace!("region {} -> {} edges", region, result);
                     result
                 })
                 .sum::<usize>()
@@ -249,24 +257,30 @@ fn p2(plots: &Vec<Vec<char>>) -> usize {
         .sum()
 }
 
-pub fn d12p1(file_path: &str) -> usize {
-    let plots = matrix::as_char_matrix(file_path);
-    p1(&plots)
+pub fn d12p1(plots: &Vec<Vec<char>>) -> usize {
+    p1(plots)
 }
 
-pub fn d12p2(file_path: &str) -> usize {
-    let plots = matrix::as_char_matrix(file_path);
-    p2(&plots)
+pub fn d12p2(plots: &Vec<Vec<char>>) -> usize {
+    p2(plots)
 }
 
-pub fn d12() {
-    // let file_path = "inputs/d12sample1.txt";
-    // let file_path = "inputs/d12sample2.txt";
-    let file_path = "inputs/d12.txt";
-    let mut result = d12p1(file_path);
-    println!("Result Day 12 Part 1: {}", result);
-    result = d12p2(file_path);
-    println!("Result Day 12 Part 2: {}", result);
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        Ok(matrix::as_char_matrix_from_str(&input.text))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d12p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d12p2(input).into()
+    }
 }
 
 #[cfg(test)]
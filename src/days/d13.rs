@@ -1,65 +1,80 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
+use crate::utils::{self, Line, ParseError, Source};
 use regex::Regex;
 
-fn parse_button(raw_button: &str) -> (i64, i64) {
-    let re = Regex::new(r"X\+(\d+), Y\+(\d+)").unwrap();
+// the "X+94, Y+34" or "X=8400, Y=5400" after the label at the start of the line
+fn parse_xy(line: &Line, re: &Regex, expected: &str) -> Result<(i64, i64), ParseError> {
+    let raw = line.text.split(": ").nth(1).unwrap_or("");
+    let (_, [x_str, y_str]) = re
+        .captures(raw)
+        .ok_or_else(|| line.error(raw, format!("expected {}", expected)))?
+        .extract();
+    Ok((
+        line.parse(x_str, "an integer")?,
+        line.parse(y_str, "an integer")?,
+    ))
+}
 
-    let (_, [x_str, y_str]) = re.captures(raw_button).unwrap().extract();
-    let x = x_str.parse().expect("integer expected");
-    let y = y_str.parse().expect("integer expected");
-    (x, y)
+fn parse_button(line: &Line) -> Result<(i64, i64), ParseError> {
+    let re = Regex::new(r"X\+(\d+), Y\+(\d+)").unwrap();
+    parse_xy(line, &re, "X+<n>, Y+<n>")
 }
 
-fn parse_prize(raw_prize: &str) -> (i64, i64) {
+fn parse_prize(line: &Line) -> Result<(i64, i64), ParseError> {
     let re = Regex::new(r"X\=(\d+), Y\=(\d+)").unwrap();
-
-    let (_, [x_str, y_str]) = re.captures(raw_prize).unwrap().extract();
-    let x: i64 = x_str.parse().expect("integer expected");
-    let y: i64 = y_str.parse().expect("integer expected");
-    (x, y)
+    parse_xy(line, &re, "X=<n>, Y=<n>")
 }
 
+type Machine = (i64, i64, i64, i64, i64, i64);
+
 // parse
 // Button A: X+94, Y+34
 // Button B: X+22, Y+67
 // Prize: X=8400, Y=5400
 // into (94, 22, 8400, 34, 67, 5400)
-fn parse(file_path: &str) -> Vec<(i64, i64, i64, i64, i64, i64)> {
+fn parse(input: &Source) -> Result<Vec<Machine>, ParseError> {
     let mut result = vec![];
 
-    let mut lines = utils::string_iter(file_path);
-    while let Some(button_a_line) = lines.next() {
-        let (ax, ay) = parse_button(button_a_line.split(": ").skip(1).next().unwrap());
-        let (bx, by) = parse_button(lines.next().unwrap().split(": ").skip(1).next().unwrap());
-        let (x_prize, y_prize) =
-            parse_prize(lines.next().unwrap().split(": ").skip(1).next().unwrap());
+    let mut lines = input.lines();
+    let mut next_line = |what: &str| {
+        lines
+            .next()
+            .ok_or_else(|| input.error(format!("ended before the {} line", what)))
+    };
+    while let Ok(button_a_line) = next_line("button A") {
+        let (ax, ay) = parse_button(&button_a_line)?;
+        let (bx, by) = parse_button(&next_line("button B")?)?;
+        let (x_prize, y_prize) = parse_prize(&next_line("prize")?)?;
 
         result.push((ax, bx, x_prize, ay, by, y_prize));
 
-        // consume empty separator line or EOF
-        lines.next();
+        // an empty separator line, or the end of the input
+        if let Ok(separator) = next_line("separator") {
+            if !separator.text.is_empty() {
+                return Err(separator.error("", "expected an empty line between machines"));
+            }
+        }
     }
 
-    result
+    Ok(result)
 }
 
-pub fn d13p1(file_path: &str) -> u64 {
-    parse(file_path)
+pub fn d13p1(machines: &Vec<(i64, i64, i64, i64, i64, i64)>) -> u64 {
+    machines
         .iter()
-        .map(|(ax, bx, x_prize, ay, by, y_prize)| {
+        .filter_map(|(ax, bx, x_prize, ay, by, y_prize)| {
             utils::simultaneous_equations_posint_result(ax, bx, x_prize, ay, by, y_prize)
-        })
-        .flatten() // drop the nones
+        }) // drop the nones
         .map(|(a_button_presses, b_button_presses)| 3 * a_button_presses + b_button_presses)
         .sum()
 }
 
 const P2_FACTOR: i64 = 10000000000000;
 
-pub fn d13p2(file_path: &str) -> u64 {
-    parse(file_path)
+pub fn d13p2(machines: &Vec<(i64, i64, i64, i64, i64, i64)>) -> u64 {
+    machines
         .iter()
-        .map(|(ax, bx, x_prize, ay, by, y_prize)| {
+        .filter_map(|(ax, bx, x_prize, ay, by, y_prize)| {
             utils::simultaneous_equations_posint_result(
                 ax,
                 bx,
@@ -68,17 +83,56 @@ pub fn d13p2(file_path: &str) -> u64 {
                 by,
                 &(y_prize + P2_FACTOR),
             )
-        })
-        .flatten() // drop the nones
+        }) // drop the nones
         .map(|(a_button_presses, b_button_presses)| 3 * a_button_presses + b_button_presses)
         .sum()
 }
 
-pub fn d13() {
-    //let file_path = "inputs/d13sample.txt";
-    let file_path = "inputs/d13.txt";
-    let mut result = d13p1(file_path);
-    println!("Result Day 13 Part 1: {}", result);
-    result = d13p2(file_path);
-    println!("Result Day 13 Part 2: {}", result);
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<(i64, i64, i64, i64, i64, i64)>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d13p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d13p2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_button_test() {
+        let line = Line::new("test", 1, "Button A: X+94, Y+34");
+        assert_eq!(parse_button(&line), Ok((94, 34)));
+
+        let err = parse_button(&Line::new("d13.txt", 5, "Button B: X+22 Y+67")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "d13.txt:5:11: expected X+<n>, Y+<n>: 'X+22 Y+67'"
+        );
+    }
+
+    #[test]
+    fn parse_test() {
+        let machine = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n";
+        let two = Source::new("d13.txt", &format!("{}\n{}", machine, machine));
+        assert_eq!(parse(&two).unwrap().len(), 2);
+
+        let joined = Source::new("d13.txt", &format!("{}{}", machine, machine));
+        let err = parse(&joined).unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (4, "expected an empty line between machines")
+        );
+    }
 }
@@ -1,28 +1,37 @@
-use crate::utils;
-use crate::utils::matrix;
+use crate::solution::{Answer, Solution};
+use crate::utils::{ParseError, Source};
+use log::debug;
 use regex::Regex;
-use std::io;
 
 // parse lines like p=0,4 v=3,-3 into (0, 4, 3, -3)
-fn parse(file_path: &str) -> Vec<(isize, isize, isize, isize)> {
+fn parse(input: &Source) -> Result<Vec<(isize, isize, isize, isize)>, ParseError> {
     let re = Regex::new(r"p\=(-?\d+),(-?\d+) v\=(-?\d+),(-?\d+)").unwrap();
 
     let mut result = vec![];
 
-    let mut lines = utils::string_iter(file_path);
-    while let Some(robot_line) = lines.next() {
-        let (_, [px_str, py_str, vx_str, vy_str]) =
-            re.captures(robot_line.as_str()).unwrap().extract();
-        let px = px_str.parse().expect("integer expected");
-        let py = py_str.parse().expect("integer expected");
-        let vx = vx_str.parse().expect("integer expected");
-        let vy = vy_str.parse().expect("integer expected");
+    for line in input.lines() {
+        let (_, [px_str, py_str, vx_str, vy_str]) = re
+            .captures(line.text)
+            .ok_or_else(|| line.error("", "expected p=<x>,<y> v=<x>,<y>"))?
+            .extract();
+        let px = line.parse(px_str, "an integer")?;
+        let py = line.parse(py_str, "an integer")?;
+        let vx = line.parse(vx_str, "an integer")?;
+        let vy = line.parse(vy_str, "an integer")?;
 
-        // println!("{} {} {} {}", px, py, vx, vy);
         result.push((px, py, vx, vy));
     }
 
-    result
+    Ok(result)
+}
+
+// the sample input uses a smaller space than the real input, which every one of its robots starts inside
+fn dimensions(robots: &Vec<(isize, isize, isize, isize)>) -> (usize, usize) {
+    if robots.iter().all(|(px, py, _, _)| *px < 11 && *py < 7) {
+        (11, 7)
+    } else {
+        (101, 103)
+    }
 }
 
 // what is the new location of the robot after N moves, with wraparound?
@@ -74,74 +83,112 @@ fn safety_factor(x_total: usize, y_total: usize, robots: &Vec<(isize, isize)>) -
     low_low * low_high * high_low * high_high
 }
 
-pub fn d14p1(file_path: &str, x_total: usize, y_total: usize) -> usize {
-    let robot_starts = parse(file_path);
-    let robot_ends = move_all_n(x_total, y_total, &robot_starts, 100);
+pub fn d14p1(
+    robot_starts: &Vec<(isize, isize, isize, isize)>,
+    x_total: usize,
+    y_total: usize,
+) -> usize {
+    let robot_ends = move_all_n(x_total, y_total, robot_starts, 100);
     safety_factor(x_total, y_total, &robot_ends)
 }
 
-fn print_robots_after_n(
+// the robots as they would be drawn after N moves
+fn render_robots_after_n(
     x_total: usize,
     y_total: usize,
     robots: &Vec<(isize, isize, isize, isize)>,
     n: isize,
-) -> isize {
+) -> Vec<String> {
     let mut to_print = vec![vec!['.'; x_total]; y_total];
 
     for robot in move_all_n(x_total, y_total, robots, n) {
         let (px, py) = robot;
         to_print[py as usize][px as usize] = '#';
     }
-    matrix::pretty_print(&to_print);
-    println!("{}", n);
-    n
+    to_print.iter().map(|row| row.iter().collect()).collect()
 }
 
-pub fn d14p2(file_path: &str, x_total: usize, y_total: usize) -> usize {
-    let robot_starts = parse(file_path);
-    let mut n = 0;
-
-    loop {
-        println!("move robots (Y/n)?");
-
-        let mut yesno: String = String::new();
-        io::stdin()
-            .read_line(&mut yesno)
-            .expect("Failed to read line");
-        if yesno.contains("n") {
-            break;
-        } else {
-            n += 1;
-            print_robots_after_n(x_total, y_total, &robot_starts, n);
-        }
+// n^2 times the variance of n values, which is enough to compare how spread out sets of the same
+// number of values are without leaving integers
+fn spread(values: &Vec<isize>) -> isize {
+    let n = values.len() as isize;
+    let sum: isize = values.iter().sum();
+    let sum_of_squares: isize = values.iter().map(|v| v * v).sum();
+    n * sum_of_squares - sum * sum
+}
+
+// along one axis every robot is back where it started after `size` moves, so the moves within that
+// period where the robots bunch up the most (the tree is drawn) repeat every period
+fn least_spread_step(starts: &Vec<(isize, isize)>, size: usize) -> usize {
+    (0..size)
+        .min_by_key(|&n| {
+            let positions = starts
+                .iter()
+                .map(|&(p, v)| (p + n as isize * v).rem_euclid(size as isize))
+                .collect();
+            spread(&positions)
+        })
+        .unwrap()
+}
+
+// the step that is x_step more than a multiple of x_total and y_step more than a multiple of y_total.
+// There is exactly one below x_total * y_total when the two sizes have no common factor, as 101 and
+// 103 don't (Chinese remainder theorem)
+fn combine_steps(x_step: usize, x_total: usize, y_step: usize, y_total: usize) -> Option<usize> {
+    (0..y_total)
+        .map(|k| x_step + k * x_total)
+        .find(|n| n % y_total == y_step)
+}
+
+pub fn d14p2(
+    robot_starts: &Vec<(isize, isize, isize, isize)>,
+    x_total: usize,
+    y_total: usize,
+) -> usize {
+    // the tree bunches the robots up along both axes at once, and each axis can be looked at alone
+    let xs = robot_starts
+        .iter()
+        .map(|&(px, _, vx, _)| (px, vx))
+        .collect();
+    let ys = robot_starts
+        .iter()
+        .map(|&(_, py, _, vy)| (py, vy))
+        .collect();
+    let x_step = least_spread_step(&xs, x_total);
+    let y_step = least_spread_step(&ys, y_total);
+    debug!(
+        "bunched up across after {} moves and down after {}",
+        x_step, y_step
+    );
+
+    let n = combine_steps(x_step, x_total, y_step, y_total)
+        .expect("the width and height have a common factor");
+    for line in render_robots_after_n(x_total, y_total, robot_starts, n as isize) {
+        debug!("{}", line);
     }
+    n
+}
 
-    // empirically, I observed the robots clustering:
-    // vertically at 38, 139, 240 (cycle = 101)
-    // horizontally at 88, 191 (cycle = 103)
+pub struct Day14;
 
-    // where do these cycles converge?
-    // n = 38 + 101 * x
-    // n = 88 + 103 * x
-    // x = -25, n = -2487
+impl Solution for Day14 {
+    type Input = (Vec<(isize, isize, isize, isize)>, (usize, usize));
 
-    n = -2487;
-    // YES I do see it there
-    print_robots_after_n(x_total, y_total, &robot_starts, n);
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        let robots = parse(input)?;
+        let dimensions = dimensions(&robots);
+        Ok((robots, dimensions))
+    }
 
-    // try converting to positive using wraparound modulo logic
-    n.rem_euclid((x_total * y_total) as isize) as usize
-}
+    fn part1(&self, input: &Self::Input) -> Answer {
+        let (robots, (x_total, y_total)) = input;
+        d14p1(robots, *x_total, *y_total).into()
+    }
 
-pub fn d14() {
-    // let file_path = "inputs/d14sample.txt";
-    // let (x_total, y_total) = (11, 7); // sample input
-    let file_path = "inputs/d14.txt";
-    let (x_total, y_total) = (101, 103); // real input
-    let mut result = d14p1(file_path, x_total, y_total);
-    println!("Result Day 14 Part 1: {}", result);
-    result = d14p2(file_path, x_total, y_total);
-    println!("Result Day 14 Part 2: {}", result);
+    fn part2(&self, input: &Self::Input) -> Answer {
+        let (robots, (x_total, y_total)) = input;
+        d14p2(robots, *x_total, *y_total).into()
+    }
 }
 
 #[cfg(test)]
@@ -158,4 +205,27 @@ mod tests {
         assert_eq!(move_n(3, 3, 0, 0, 1, 1, 5), (2, 2));
         assert_eq!(move_n(3, 3, 0, 0, -1, -1, 5), (1, 1));
     }
+
+    #[test]
+    fn least_spread_step_test() {
+        assert_eq!(spread(&vec![3, 3, 3]), 0);
+        assert_eq!(spread(&vec![1, 2, 3]), 6);
+
+        // all three meet at 0 after 3 moves
+        assert_eq!(least_spread_step(&vec![(4, 1), (1, 2), (5, 3)], 7), 3);
+    }
+
+    #[test]
+    fn combine_steps_test() {
+        // the steps the robots were seen bunching up at, by hand
+        assert_eq!(combine_steps(38, 101, 88, 103), Some(7916));
+        assert_eq!(combine_steps(0, 101, 0, 103), Some(0));
+        assert_eq!(combine_steps(1, 4, 0, 6), None);
+    }
+
+    #[test]
+    fn render_robots_test() {
+        let robots = vec![(0, 0, 1, 1), (2, 1, -1, 0)];
+        assert_eq!(render_robots_after_n(3, 2, &robots, 1), vec!["...", ".#."]);
+    }
 }
@@ -1,9 +1,11 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
 use crate::utils::distinct;
 use crate::utils::matrix;
 use crate::utils::plane;
 use crate::utils::plane::Direction;
 use crate::utils::plane::MovingObject;
+use crate::utils::{ParseError, Source};
+use log::trace;
 
 const ROBOT: char = '@';
 const WALL: char = '#';
@@ -12,23 +14,37 @@ const EMPTY: char = '.';
 const L_BOX: char = '[';
 const R_BOX: char = ']';
 
-fn parse(file_path: &str) -> (Vec<Vec<char>>, Vec<Direction>) {
-    let mut iter = utils::string_iter(file_path);
+fn parse(input: &Source) -> Result<(Vec<Vec<char>>, Vec<Direction>), ParseError> {
+    let mut iter = input.lines();
 
     let mut mat = vec![];
-    while let Some(row) = iter.next() {
-        if row.is_empty() {
+    for row in iter.by_ref() {
+        if row.text.is_empty() {
             break;
         }
-        mat.push(matrix::to_char_vec(row));
+        for (col, c) in row.text.char_indices() {
+            if ![WALL, BOX, ROBOT, EMPTY].contains(&c) {
+                return Err(row.error(
+                    &row.text[col..col + c.len_utf8()],
+                    "expected a wall, box, robot or empty space",
+                ));
+            }
+        }
+        mat.push(matrix::to_char_vec(row.text.to_string()));
     }
+    plane::check_unique_element(input, &mat, ROBOT, "robot")?;
 
     let mut moves = vec![];
     for line in iter {
-        moves.extend(line.chars().map(Direction::from_char));
+        for (col, c) in line.text.char_indices() {
+            if !"^v<>".contains(c) {
+                return Err(line.error(&line.text[col..col + c.len_utf8()], "expected a move"));
+            }
+            moves.push(Direction::from_char(c));
+        }
     }
 
-    (mat, moves)
+    Ok((mat, moves))
 }
 
 fn push_box_1(
@@ -40,7 +56,14 @@ fn push_box_1(
     let row_count = mat.len();
     let col_count = mat[0].len();
 
-    // println!("Pushing box at ({}, {}) in direction {:?} - {},{}", row, col, dir, row_count, col_count);
+    trace!(
+        "Pushing box at ({}, {}) in direction {:?} - {},{}",
+        robot_row,
+        robot_col,
+        dir,
+        row_count,
+        col_count
+    );
 
     let box_1 = plane::move_one(robot_row, robot_col, row_count, col_count, dir);
     let mut box_spaces = vec![(box_1.row, box_1.col)];
@@ -124,10 +147,15 @@ fn push_p2_ud_box_1(
     let box_1_half = plane::move_one(robot_row, robot_col, row_count, col_count, dir);
     let box_1 = complete_box_from_half(&box_1_half, which_box, dir);
 
-    // println!(
-    //     "Pushing box at ({}, {}-{}) in direction {:?} - total matrix size {},{}",
-    //     box_1_half.row, box_1[0].col, box_1[1].col, dir, row_count, col_count
-    // );
+    trace!(
+        "Pushing box at ({}, {}-{}) in direction {:?} - total matrix size {},{}",
+        box_1_half.row,
+        box_1[0].col,
+        box_1[1].col,
+        dir,
+        row_count,
+        col_count
+    );
 
     // in the vertical direction, it's possible for a box to push more than one other box
     // so we need to account for pushes by all segments
@@ -147,18 +175,16 @@ fn push_p2_ud_box_1(
         if obstacles_ahead.contains(&WALL) {
             // hit a wall before an open space; can't move
 
-            // println!();
-            // println!("Hit wall. Current box row: {:?}", current_box_row);
+            trace!("Hit wall. Current box row: {:?}", current_box_row);
 
             return (mat, robot_row, robot_col);
         } else if obstacles_ahead.is_empty() {
             // can move into empty space
 
-            // println!();
-            // println!(
-            //     "Moving into open space. Current box row: {:?}",
-            //     current_box_row
-            // );
+            trace!(
+                "Moving into open space. Current box row: {:?}",
+                current_box_row
+            );
 
             // from end of box_rows to start:
             // move src row to dest row, then set src row to EMPTY
@@ -208,9 +234,8 @@ fn push_p2_ud_box_1(
                     .collect(),
             );
 
-            // println!();
-            // println!("Hit box(es).  current row: {:?} ", current_box_row);
-            // println!("next row: {:?}", box_row);
+            trace!("Hit box(es).  current row: {:?} ", current_box_row);
+            trace!("next row: {:?}", box_row);
 
             box_rows.push(box_row.clone());
             current_box_row = box_row;
@@ -227,7 +252,14 @@ fn move_robot_1(
     let row_count = mat.len();
     let col_count = mat[0].len();
 
-    // println!("Moving robot at ({}, {}) in direction {:?} - {},{}", row, col, dir, row_count, col_count);
+    trace!(
+        "Moving robot at ({}, {}) in direction {:?} - {},{}",
+        row,
+        col,
+        dir,
+        row_count,
+        col_count
+    );
 
     let robot = MovingObject {
         row,
@@ -243,7 +275,7 @@ fn move_robot_1(
         (Some(WALL), _) =>
         // can't move
         {
-            return (mat, row, col)
+            (mat, row, col)
         }
         (Some(BOX), _) => push_box_1(mat, row, col, dir),
         // can use P1 for Left and Right because pushing at broundaries doesn't come into play
@@ -266,17 +298,17 @@ fn gps_score(row: usize, col: usize) -> usize {
 }
 
 fn score(mat: &Vec<Vec<char>>, target: char) -> usize {
-    plane::find_all_elements(&mat, target)
+    plane::find_all_elements(mat, target)
         .iter()
         .map(|&(row, col)| gps_score(row, col))
         .sum()
 }
 
-pub fn d15p1(file_path: &str) -> usize {
-    let (mut mat, moves) = parse(file_path);
+pub fn d15p1((mat, moves): &(Vec<Vec<char>>, Vec<Direction>)) -> usize {
+    let mut mat = mat.clone();
     let (mut robot_row, mut robot_col) = plane::find_unique_element(&mat, ROBOT);
     for move_1 in moves {
-        (mat, robot_row, robot_col) = move_robot_1(mat, robot_row, robot_col, move_1);
+        (mat, robot_row, robot_col) = move_robot_1(mat, robot_row, robot_col, *move_1);
     }
     score(&mat, BOX)
 }
@@ -300,24 +332,31 @@ fn expand(mat: &Vec<Vec<char>>) -> Vec<Vec<char>> {
     result
 }
 
-pub fn d15p2(file_path: &str) -> usize {
-    let (mut mat, moves) = parse(file_path);
-    mat = expand(&mat);
+pub fn d15p2((mat, moves): &(Vec<Vec<char>>, Vec<Direction>)) -> usize {
+    let mut mat = expand(mat);
     let (mut robot_row, mut robot_col) = plane::find_unique_element(&mat, ROBOT);
     for move_1 in moves {
-        (mat, robot_row, robot_col) = move_robot_1(mat, robot_row, robot_col, move_1);
+        (mat, robot_row, robot_col) = move_robot_1(mat, robot_row, robot_col, *move_1);
     }
     score(&mat, L_BOX)
 }
 
-pub fn d15() {
-    // let file_path = "inputs/d15sample1.txt";
-    // let file_path = "inputs/d15sample2.txt";
-    let file_path = "inputs/d15.txt";
-    let mut result = d15p1(file_path);
-    println!("Result Day 15 Part 1: {}", result);
-    result = d15p2(file_path);
-    println!("Result Day 15 Part 2: {}", result);
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (Vec<Vec<char>>, Vec<Direction>);
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d15p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d15p2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -328,6 +367,18 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_parse() {
+        let (mat, moves) = parse(&Source::new("ok", "#@O.\n\n<>\n")).unwrap();
+        assert_eq!(mat, vec![vec![WALL, ROBOT, BOX, EMPTY]]);
+        assert_eq!(moves, vec![Direction::Left, Direction::Right]);
+
+        let err = parse(&Source::new("bad", "#@x.\n\n<\n")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 3, "x"));
+        assert!(parse(&Source::new("no robot", "#O.\n\n<\n")).is_err());
+        assert!(parse(&Source::new("two robots", "@@\n\n<\n")).is_err());
+    }
+
     #[test]
     fn test_push_box_1() {
         // no change because we hit the wall (p1)
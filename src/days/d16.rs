@@ -1,5 +1,7 @@
+use crate::solution::{Answer, Solution};
 use crate::utils::matrix;
 use crate::utils::plane::{self, Direction, MovingObject};
+use crate::utils::{ParseError, Source};
 
 use multimap::MultiMap;
 use priority_queue::PriorityQueue;
@@ -68,8 +70,8 @@ fn get_neighbors(maze: &Vec<Vec<char>>, curr: &MovingObject) -> Vec<(MovingObjec
         ),
     ];
 
-    if plane::obstacle_ahead(&maze, &vec![WALL], &curr).is_none() {
-        let (row_count, col_count) = matrix::dimensions(&maze);
+    if plane::obstacle_ahead(maze, &vec![WALL], curr).is_none() {
+        let (row_count, col_count) = matrix::dimensions(maze);
 
         neighbors.push((
             plane::move_one(curr.row, curr.col, row_count, col_count, curr.dir),
@@ -89,7 +91,7 @@ fn visit_neighbor(
     additional_score: &usize,
 ) {
     let new_score = score.get(current).unwrap() + additional_score;
-    let existing_score = score.get(&neighbor).unwrap();
+    let existing_score = score.get(neighbor).unwrap();
     if new_score <= *existing_score {
         score.insert(neighbor.clone(), new_score);
         previous_nodes.insert(neighbor.clone(), current.clone());
@@ -107,7 +109,7 @@ fn score_maze(
 ) {
     let (mut to_visit, mut score) = init_structs(maze);
 
-    let (start_row, start_col) = plane::find_unique_element(&maze, START);
+    let (start_row, start_col) = plane::find_unique_element(maze, START);
 
     let start_node = MovingObject {
         row: start_row,
@@ -121,7 +123,7 @@ fn score_maze(
     let mut previous_nodes: MultiMap<MovingObject, MovingObject> = MultiMap::new();
 
     while let Some(current) = to_visit.pop().map(|(mo, _)| mo) {
-        get_neighbors(&maze, &current)
+        get_neighbors(maze, &current)
             .iter()
             .for_each(|(neighbor, additional_score)| {
                 visit_neighbor(
@@ -139,7 +141,7 @@ fn score_maze(
 }
 
 fn min_score(maze: &Vec<Vec<char>>, score: &HashMap<MovingObject, usize>) -> usize {
-    let (end_row, end_col) = plane::find_unique_element(&maze, END);
+    let (end_row, end_col) = plane::find_unique_element(maze, END);
 
     let min_score = Direction::all()
         .iter()
@@ -161,9 +163,8 @@ fn shortest_path(maze: &Vec<Vec<char>>) -> usize {
     min_score(maze, &score)
 }
 
-pub fn d16p1(file_path: &str) -> usize {
-    let maze = matrix::as_char_matrix(file_path);
-    shortest_path(&maze)
+pub fn d16p1(maze: &Vec<Vec<char>>) -> usize {
+    shortest_path(maze)
 }
 
 fn shortest_path_for_node(
@@ -177,7 +178,7 @@ fn shortest_path_for_node(
         return nodes_so_far.clone();
     }
 
-    let Some(next_nodes) = prev_nodes.get_vec(&current) else {
+    let Some(next_nodes) = prev_nodes.get_vec(current) else {
         return HashSet::new();
     };
 
@@ -201,8 +202,8 @@ fn shortest_path_for_node(
 fn tiles_along_shortest_paths(maze: &Vec<Vec<char>>) -> usize {
     let (score, prev_nodes) = score_maze(maze);
 
-    let (start_row, start_col) = plane::find_unique_element(&maze, START);
-    let (end_row, end_col) = plane::find_unique_element(&maze, END);
+    let (start_row, start_col) = plane::find_unique_element(maze, START);
+    let (end_row, end_col) = plane::find_unique_element(maze, END);
 
     let shortest_paths: HashSet<(usize, usize)> = Direction::all()
         .iter()
@@ -222,19 +223,29 @@ fn tiles_along_shortest_paths(maze: &Vec<Vec<char>>) -> usize {
     shortest_paths.len()
 }
 
-pub fn d16p2(file_path: &str) -> usize {
-    let maze = matrix::as_char_matrix(file_path);
-    tiles_along_shortest_paths(&maze)
+pub fn d16p2(maze: &Vec<Vec<char>>) -> usize {
+    tiles_along_shortest_paths(maze)
 }
 
-pub fn d16() {
-    // let file_path = "inputs/d16sample1.txt";
-    // let file_path = "inputs/d16sample2.txt";
-    let file_path = "inputs/d16.txt";
-    let mut result = d16p1(file_path);
-    println!("Result Day 16 Part 1: {}", result);
-    result = d16p2(file_path);
-    println!("Result Day 16 Part 2: {}", result);
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        let maze = matrix::as_char_matrix_from_str(&input.text);
+        plane::check_unique_element(input, &maze, START, "start")?;
+        plane::check_unique_element(input, &maze, END, "end")?;
+        Ok(maze)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d16p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d16p2(input).into()
+    }
 }
 
 #[cfg(test)]
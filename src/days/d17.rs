@@ -1,5 +1,13 @@
+use crate::solution::{Answer, Solution};
+use crate::utils::{Line, ParseError, Source};
+use log::{debug, trace};
+use vm::Machine;
+
+// the most values a quine search tries for chunks of A before giving up
+const MAX_TRIES: usize = 100_000;
+
 #[derive(Clone, PartialEq, Debug)]
-struct State {
+pub struct State {
     reg_a: usize,
     reg_b: usize,
     reg_c: usize,
@@ -7,6 +15,46 @@ struct State {
     output: Vec<u8>,
 }
 
+impl State {
+    // the registers a program starts with, before it has run or output anything
+    pub fn new(reg_a: usize, reg_b: usize, reg_c: usize) -> State {
+        State {
+            reg_a,
+            reg_b,
+            reg_c,
+            inst_ptr: 0,
+            output: vec![],
+        }
+    }
+
+    pub fn reg_a(&self) -> usize {
+        self.reg_a
+    }
+
+    pub fn reg_b(&self) -> usize {
+        self.reg_b
+    }
+
+    pub fn reg_c(&self) -> usize {
+        self.reg_c
+    }
+
+    pub fn inst_ptr(&self) -> usize {
+        self.inst_ptr
+    }
+
+    pub fn output(&self) -> &[u8] {
+        &self.output
+    }
+}
+
+pub mod asm;
+pub mod debugger;
+pub mod disasm;
+pub mod symbolic;
+pub mod vm;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
 enum OpCode {
     Adv,
     Bdv,
@@ -32,214 +80,360 @@ fn to_opcode(code: u8) -> OpCode {
     }
 }
 
-// some operands are "combo" operands
-fn combo(state: &State, operand: u8) -> usize {
-    match operand {
-        0..=3 => operand as usize,
-        4 => state.reg_a,
-        5 => state.reg_b,
-        6 => state.reg_c,
-        _ => panic!("Invalid combo operand"),
+impl OpCode {
+    fn mnemonic(&self) -> &'static str {
+        match self {
+            OpCode::Adv => "adv",
+            OpCode::Bdv => "bdv",
+            OpCode::Cdv => "cdv",
+            OpCode::Bxl => "bxl",
+            OpCode::Bst => "bst",
+            OpCode::Jnz => "jnz",
+            OpCode::Bxc => "bxc",
+            OpCode::Out => "out",
+        }
     }
-}
-
-// integer division: A = A / 2^combo(operand)
-fn op_adv(state: &State, operand: u8) -> State {
-    let mut new_state = state.clone();
-    new_state.reg_a = state.reg_a / (2 as usize).pow(combo(&state, operand) as u32);
-    new_state
-}
-
-// integer division: B = A / 2^combo(operand)
-fn op_bdv(state: &State, operand: u8) -> State {
-    let mut new_state = state.clone();
-    new_state.reg_b = state.reg_a / (2 as usize).pow(combo(&state, operand) as u32);
-    new_state
-}
-
-// integer division: C = A / 2^combo(operand)
-fn op_cdv(state: &State, operand: u8) -> State {
-    let mut new_state = state.clone();
-    new_state.reg_c = state.reg_a / (2 as usize).pow(combo(&state, operand) as u32);
-    new_state
-}
-
-// B = bitwise xor of B and operand
-fn op_bxl(state: &State, operand: u8) -> State {
-    let mut new_state = state.clone();
-    new_state.reg_b = state.reg_b ^ operand as usize;
-    new_state
-}
 
-// B = combo operand mod 8
-fn op_bst(state: &State, operand: u8) -> State {
-    let mut new_state = state.clone();
-    new_state.reg_b = combo(&state, operand) % 8;
-    new_state
+    // whether the operand is a combo operand, which reads a register for 4 to 6, rather than a literal
+    fn takes_combo(&self) -> bool {
+        matches!(
+            self,
+            OpCode::Adv | OpCode::Bdv | OpCode::Cdv | OpCode::Bst | OpCode::Out
+        )
+    }
 }
 
-// jump to operand if A is not zero
-fn op_jnz(state: &State, operand: u8) -> State {
-    if state.reg_a == 0 {
-        state.clone()
-    } else {
-        let mut new_state = state.clone();
-        new_state.inst_ptr = operand as usize;
-        return new_state;
+// what a combo operand reads, as it would be written down
+fn combo_name(operand: u8) -> String {
+    match operand {
+        0..=3 => operand.to_string(),
+        4 => "A".to_string(),
+        5 => "B".to_string(),
+        6 => "C".to_string(),
+        _ => "?".to_string(),
     }
 }
 
-// B = B xor C
-fn op_bxc(state: &State) -> State {
-    let mut new_state = state.clone();
-    new_state.reg_b = state.reg_b ^ state.reg_c;
-    new_state
+// the text after "<label>: " on lines like "Register A: 729" and "Program: 0,1,5,4,3,0"
+fn labelled<'a>(line: &Line<'a>, label: &str) -> Result<&'a str, ParseError> {
+    line.text
+        .strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(": "))
+        .ok_or_else(|| line.error(line.text, format!("expected '{}: '", label)))
 }
 
-// add combo operand mod 8 to output
-fn op_out(state: &State, operand: u8) -> State {
-    let mut new_state = state.clone();
-    new_state.output.push(combo(&state, operand) as u8 % 8);
-    new_state
+// every opcode and operand is a 3-bit number, and they come in pairs. Combo operand 7 is reserved,
+// so it can't follow an opcode that takes a combo operand
+fn parse_program(line: &Line) -> Result<Vec<u8>, ParseError> {
+    let values = labelled(line, "Program")?;
+    let mut prog = vec![];
+    for (idx, value) in values.split(',').enumerate() {
+        let what = if idx % 2 == 0 {
+            "an opcode from 0 to 7"
+        } else {
+            "an operand from 0 to 7"
+        };
+        let n: u8 = line.parse(value, what)?;
+        if n > 7 {
+            return Err(line.error(value, format!("expected {}", what)));
+        }
+        if idx % 2 == 1 && n == 7 && to_opcode(prog[idx - 1]).takes_combo() {
+            return Err(line.error(value, "combo operand 7 is reserved"));
+        }
+        prog.push(n);
+    }
+    if prog.len() % 2 != 0 {
+        return Err(line.error("", "expected an operand after the last opcode"));
+    }
+    Ok(prog)
 }
 
-fn op(state: &State, opcode: u8, operand: u8) -> State {
-    let mut new_state = match to_opcode(opcode) {
-        OpCode::Adv => op_adv(state, operand),
-        OpCode::Bdv => op_bdv(state, operand),
-        OpCode::Cdv => op_cdv(state, operand),
-        OpCode::Bxl => op_bxl(state, operand),
-        OpCode::Bst => op_bst(state, operand),
-        OpCode::Jnz => op_jnz(state, operand),
-        OpCode::Bxc => op_bxc(state),
-        OpCode::Out => op_out(state, operand),
+// parse
+// Register A: 729
+// Register B: 0
+// Register C: 0
+//
+// Program: 0,1,5,4,3,0
+fn parse(input: &Source) -> Result<(State, Vec<u8>), ParseError> {
+    let mut lines = input.lines();
+    let mut next_line = |what: &str| {
+        lines
+            .next()
+            .ok_or_else(|| input.error(format!("ended before the {} line", what)))
     };
 
-    // if no jump
-    if state.inst_ptr == new_state.inst_ptr {
-        new_state.inst_ptr += 2;
+    let mut registers = vec![];
+    for name in ["A", "B", "C"] {
+        let line = next_line(&format!("register {}", name))?;
+        let value = labelled(&line, &format!("Register {}", name))?;
+        registers.push(line.parse(value, "an integer")?);
+    }
+    let separator = next_line("separator")?;
+    if !separator.text.trim().is_empty() {
+        return Err(separator.error("", "expected an empty line before the program"));
+    }
+    let prog = parse_program(&next_line("program")?)?;
+    if let Ok(extra) = next_line("end") {
+        if !extra.text.trim().is_empty() {
+            return Err(extra.error("", "expected nothing after the program"));
+        }
     }
 
-    // println!(
-    //     "op({:?}, {}, {}) -> {:?}",
-    //     state, opcode, operand, new_state
-    // );
-
-    new_state
+    Ok((State::new(registers[0], registers[1], registers[2]), prog))
 }
 
-fn program(mut state: State, program: Vec<u8>) -> State {
-    while state.inst_ptr < program.len() {
-        let (opcode, operand) = (program[state.inst_ptr], program[state.inst_ptr + 1]);
-        state = op(&state, opcode, operand);
-    }
-    state
+fn program(mut state: State, program: Vec<u8>) -> Result<State, String> {
+    Machine::new(&program).run(&mut state)?;
+    trace!("program({:?}) -> {:?}", program, state);
+    Ok(state)
 }
 
-// manually parsed
-fn d17_input() -> (State, Vec<u8>) {
-    let state = State {
-        reg_a: 62769524,
-        reg_b: 0,
-        reg_c: 0,
-        inst_ptr: 0,
-        output: vec![],
-    };
-    let prog = vec![2, 4, 1, 7, 7, 5, 0, 3, 4, 0, 1, 7, 5, 5, 3, 0];
-    (state, prog)
+pub fn d17p1((start_state, prog): &(State, Vec<u8>)) -> Result<String, String> {
+    Ok(format_output(
+        &program(start_state.clone(), prog.clone())?.output,
+    ))
 }
 
-pub fn d17p1() -> String {
-    let (start_state, prog) = d17_input();
-    program(start_state, prog)
-        .output
+fn format_output(output: &[u8]) -> String {
+    output
         .iter()
         .map(|x| x.to_string())
         .collect::<Vec<String>>()
         .join(",")
 }
 
-fn p2_out(start_state: &State, a: usize, prog: Vec<u8>) -> String {
-    let out = program(start_state.clone(), prog)
-        .output
-        .iter()
-        .map(|x| x.to_string())
-        .collect::<Vec<String>>()
-        .join(",");
-    vec![a.to_string(), "->".to_string(), out].join(" ")
+// the run-based search relies on each loop shifting A right by 3 and nothing else jumping around
+fn shifts_a_by_3(prog: &Vec<u8>) -> bool {
+    let instructions: Vec<(OpCode, u8)> = prog
+        .chunks_exact(2)
+        .map(|inst| (to_opcode(inst[0]), inst[1]))
+        .collect();
+    let count = |opcode| instructions.iter().filter(|(op, _)| *op == opcode).count();
+    instructions.last() == Some(&(OpCode::Jnz, 0))
+        && count(OpCode::Jnz) == 1
+        && count(OpCode::Adv) == 1
+        && instructions.contains(&(OpCode::Adv, 3))
 }
 
-fn p2_manual_testing() {
-    let (mut start_state, desired_prog) = d17_input();
-
-    println!(
-        "{}",
-        desired_prog
-            .iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>()
-            .join(",")
-    );
-
-    // 0
-    start_state.reg_a = 7;
-    let mut out = p2_out(&start_state, start_state.reg_a, desired_prog.clone());
-    println!("{}", out);
-
-    // 3,0
-    start_state.reg_a = start_state.reg_a * 8 + 2;
-    out = p2_out(&start_state, start_state.reg_a, desired_prog.clone());
-    println!("{}", out);
-
-    // 5,3,0
-    start_state.reg_a = start_state.reg_a * 8 + 6;
-    out = p2_out(&start_state, start_state.reg_a, desired_prog.clone());
-    println!("{}", out);
+// whether a value of A, as the chunks found so far, produces the end of the target it is given
+type ChunkCheck<'a> = Box<dyn Fn(usize, &[u8]) -> bool + 'a>;
+
+// A is used `shift` bits at a time from the bottom, one chunk per output, so the last output only
+// depends on the top chunk of A, the output before it on the top two chunks, and so on. Working back
+// from the end of the target, a chunk is only kept if it gives the right output, and when no chunk
+// does the search backs up to try the next candidate for the chunk above. Trying each chunk's
+// values in increasing order means the first A found is the lowest.
+// Returns the lowest A that extends a_so_far, which already produces the last `matched` outputs,
+// and for which the whole program passes is_quine
+fn search_chunks(
+    shift: usize,
+    check: &ChunkCheck,
+    is_quine: &dyn Fn(usize) -> bool,
+    target: &[u8],
+    a_so_far: usize,
+    matched: usize,
+    tries_left: &mut usize,
+) -> Result<Option<usize>, String> {
+    if matched == target.len() {
+        // the check only looks at one pass of the loop, so the whole program might still disagree,
+        // in which case the search carries on from the next candidate
+        if is_quine(a_so_far) {
+            return Ok(Some(a_so_far));
+        }
+        debug!("A = {} matches every pass but not the whole run", a_so_far);
+        return Ok(None);
+    }
+    let suffix = &target[target.len() - matched - 1..];
+    for chunk in 0..1 << shift {
+        let a = a_so_far
+            .checked_mul(1 << shift)
+            .ok_or("A no longer fits in 64 bits")?
+            + chunk;
+        // the puzzle wants a positive A
+        if a == 0 {
+            continue;
+        }
+        if *tries_left == 0 {
+            return Err("that is the most allowed".to_string());
+        }
+        *tries_left -= 1;
+
+        if check(a, suffix) {
+            debug!("Matched {} -> {}", a, format_output(suffix));
+            let found = search_chunks(shift, check, is_quine, target, a, matched + 1, tries_left)?;
+            if found.is_some() {
+                return Ok(found);
+            }
+        }
+    }
+    Ok(None)
 }
 
-fn match_program(start_state: &State, desired_prog: &Vec<u8>, prog_to_match: &[u8]) -> usize {
-    let mut a = if prog_to_match.len() > 1 {
-        8 * match_program(start_state, desired_prog, &prog_to_match[1..])
-    } else {
-        1
-    };
-    let mut state = start_state.clone();
-    loop {
+// the lowest positive A for which the program outputs target, trying at most max_tries chunks.
+// Each chunk is checked by working out the output the analysis found, or for programs it can't
+// analyse but that still shift A by 3 on each pass, by running the program
+pub fn quine_search(
+    start_state: &State,
+    prog: &Vec<u8>,
+    target: &[u8],
+    max_tries: usize,
+) -> Result<usize, String> {
+    let machine = Machine::new(prog);
+    let run_with_a = |a| {
+        let mut state = start_state.clone();
         state.reg_a = a;
-        let out = program(state.clone(), desired_prog.clone()).output;
-        if out == *prog_to_match {
-            println!(
-                "Matched {} -> {}",
-                a,
-                out.iter()
-                    .map(|x| x.to_string())
-                    .collect::<Vec<String>>()
-                    .join(","),
-            );
-            break;
+        state
+    };
+    let (shift, check): (usize, ChunkCheck) = match symbolic::analyse(prog) {
+        // the chunks found so far are exactly what the pass for the first output sees as its a
+        Ok(analysis) => (
+            analysis.shift,
+            Box::new(move |a, suffix| analysis.output.eval(a) == suffix[0] as usize),
+        ),
+        Err(e) if shifts_a_by_3(prog) => {
+            debug!("can't analyse the program, so running it instead: {}", e);
+            (
+                3,
+                Box::new(|a, suffix| machine.run_matching(&mut run_with_a(a), suffix)),
+            )
         }
-        a += 1;
-    }
-    a
+        Err(e) => return Err(format!("can't search this program: {}", e)),
+    };
+    let is_quine = |a| machine.run_matching(&mut run_with_a(a), target);
+    let mut tries_left = max_tries;
+    let found = search_chunks(shift, &check, &is_quine, target, 0, 0, &mut tries_left)
+        .map_err(|e| format!("gave up after {} tries: {}", max_tries - tries_left, e))?;
+    debug!("searched with {} tries", max_tries - tries_left);
+    found.ok_or(format!(
+        "no value of A makes the program output {}",
+        format_output(target)
+    ))
 }
 
-pub fn d17p2() -> String {
-    let (start_state, desired_prog) = d17_input();
+fn match_program(
+    start_state: &State,
+    desired_prog: &Vec<u8>,
+    prog_to_match: &[u8],
+) -> Result<usize, String> {
+    quine_search(start_state, desired_prog, prog_to_match, MAX_TRIES)
+}
 
-    let a = match_program(&start_state, &desired_prog, &desired_prog);
-    a.to_string()
+// Err says why there is no A that makes the program output itself
+pub fn d17p2((start_state, desired_prog): &(State, Vec<u8>)) -> Result<usize, String> {
+    let pseudo_code = disasm::pseudo_code(desired_prog).unwrap_or_default();
+    let report = symbolic::analyse(desired_prog)
+        .map(|analysis| symbolic::report(&analysis, desired_prog.len()))
+        .unwrap_or_default();
+    for line in disasm::disassemble(desired_prog)
+        .iter()
+        .chain(&pseudo_code)
+        .chain(&report)
+    {
+        debug!("{}", line);
+    }
+    match_program(start_state, desired_prog, desired_prog)
 }
 
-pub fn d17() {
-    let mut result = d17p1();
-    println!("Result Day X Part 1: {:?}", result);
-    result = d17p2();
-    println!("Result Day X Part 2: {:?}", result);
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = (State, Vec<u8>);
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        match d17p1(input) {
+            Ok(output) => output.into(),
+            Err(reason) => Answer::Unsolved(reason),
+        }
+    }
+
+    // plenty of programs can't output themselves, e.g. the example from part 1
+    fn part2(&self, input: &Self::Input) -> Answer {
+        match d17p2(input) {
+            Ok(a) => a.into(),
+            Err(reason) => Answer::Unsolved(reason),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::solution::Solver;
+
+    const INPUT: &str = "\
+Register A: 62769524
+Register B: 0
+Register C: 0
+
+Program: 2,4,1,7,7,5,0,3,4,0,1,7,5,5,3,0
+";
+
+    // run a single instruction
+    fn op(state: &State, opcode: u8, operand: u8) -> State {
+        let mut new_state = state.clone();
+        Machine::new(&vec![opcode, operand])
+            .step(&mut new_state)
+            .unwrap();
+        new_state
+    }
+
+    fn d17_input() -> (State, Vec<u8>) {
+        parse(&Source::new("d17.txt", INPUT)).unwrap()
+    }
+
+    fn parse_error(text: &str) -> String {
+        parse(&Source::new("d17.txt", text))
+            .unwrap_err()
+            .to_string()
+    }
+
+    #[test]
+    fn parse_test() {
+        let (state, prog) = d17_input();
+        assert_eq!(state, State::new(62769524, 0, 0));
+        assert_eq!(
+            (
+                state.reg_a(),
+                state.reg_b(),
+                state.reg_c(),
+                state.inst_ptr()
+            ),
+            (62769524, 0, 0, 0)
+        );
+        assert!(state.output().is_empty());
+        assert_eq!(prog, vec![2, 4, 1, 7, 7, 5, 0, 3, 4, 0, 1, 7, 5, 5, 3, 0]);
+
+        assert_eq!(
+            parse_error("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,8,4\n"),
+            "d17.txt:5:14: expected an opcode from 0 to 7: '8'"
+        );
+        assert_eq!(
+            parse_error("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 5,7,3,0\n"),
+            "d17.txt:5:12: combo operand 7 is reserved: '7'"
+        );
+        // bxl and bxc don't take combo operands, so 7 is fine after them
+        assert!(parse(&Source::new(
+            "d17.txt",
+            "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 1,7,4,7\n"
+        ))
+        .is_ok());
+        assert_eq!(
+            parse_error("Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5\n"),
+            "d17.txt:5:15: expected an operand after the last opcode: 'Program: 0,1,5'"
+        );
+        assert_eq!(
+            parse_error("Register A: 729\nRegister C: 0\n"),
+            "d17.txt:2:1: expected 'Register B: ': 'Register C: 0'"
+        );
+        assert_eq!(
+            parse_error("Register A: 729\nRegister B: 0\nRegister C: 0\n"),
+            "d17.txt: ended before the separator line"
+        );
+    }
 
     #[test]
     fn test_op() {
@@ -296,7 +490,7 @@ mod tests {
         let mut expected_state = state.clone();
         expected_state.reg_b = 1;
         expected_state.inst_ptr = 2;
-        assert_eq!(program(state, vec![2, 6]), expected_state);
+        assert_eq!(program(state, vec![2, 6]), Ok(expected_state));
 
         // If register B contains 29, the program 1,7 would set register B to 26.
         state = State {
@@ -309,7 +503,7 @@ mod tests {
         expected_state = state.clone();
         expected_state.reg_b = 26;
         expected_state.inst_ptr = 2;
-        assert_eq!(program(state, vec![1, 7]), expected_state);
+        assert_eq!(program(state, vec![1, 7]), Ok(expected_state));
 
         // If register B contains 2024 and register C contains 43690, the program 4,0 would set register B to 44354.
         state = State {
@@ -322,7 +516,7 @@ mod tests {
         expected_state = state.clone();
         expected_state.reg_b = 44354;
         expected_state.inst_ptr = 2;
-        assert_eq!(program(state, vec![4, 0]), expected_state);
+        assert_eq!(program(state, vec![4, 0]), Ok(expected_state));
     }
 
     #[test]
@@ -336,7 +530,7 @@ mod tests {
             output: vec![],
         };
         let mut expected_output = vec![0, 1, 2];
-        let final_state = program(state, vec![5, 0, 5, 1, 5, 4]);
+        let final_state = program(state, vec![5, 0, 5, 1, 5, 4]).unwrap();
         assert_eq!(final_state.output, expected_output);
 
         // If register A contains 2024, the program 0,1,5,4,3,0 would output 4,2,5,6,7,7,7,7,3,1,0 and leave 0 in register A.
@@ -348,7 +542,7 @@ mod tests {
             output: vec![],
         };
         expected_output = vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0];
-        let final_state = program(state, vec![0, 1, 5, 4, 3, 0]);
+        let final_state = program(state, vec![0, 1, 5, 4, 3, 0]).unwrap();
         assert_eq!(final_state.output, expected_output);
         assert_eq!(final_state.reg_a, 0);
 
@@ -360,21 +554,115 @@ mod tests {
             output: vec![],
         };
         let expected_output = vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0];
-        let final_state = program(state, vec![0, 1, 5, 4, 3, 0]);
+        let final_state = program(state, vec![0, 1, 5, 4, 3, 0]).unwrap();
         assert_eq!(final_state.output, expected_output);
     }
 
     #[test]
     fn test_match_program() {
         let (start_state, desired_prog) = d17_input();
-        assert_eq!(match_program(&start_state, &desired_prog, &[0]), 7);
+        assert_eq!(match_program(&start_state, &desired_prog, &[0]), Ok(7));
         assert_eq!(
             match_program(&start_state, &desired_prog, &[3, 0]),
-            7 * 8 + 2
+            Ok(7 * 8 + 2)
         );
         assert_eq!(
             match_program(&start_state, &desired_prog, &[5, 3, 0]),
-            (7 * 8 + 2) * 8 + 6
+            Ok((7 * 8 + 2) * 8 + 6)
+        );
+    }
+
+    #[test]
+    fn did_not_halt_test() {
+        let input = Source::new(
+            "d17.txt",
+            "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,0\n",
+        );
+        assert_eq!(
+            Day17.solve_part(&input, 1),
+            Ok(Answer::Unsolved(format!(
+                "the program did not halt within {} steps",
+                vm::DEFAULT_MAX_STEPS
+            )))
+        );
+
+        let input = Source::new(
+            "d17.txt",
+            "Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,3,0,5,7,0\n",
+        );
+        assert_eq!(
+            Day17.solve_part(&input, 1),
+            Ok(Answer::Unsolved(
+                "the instruction at address 3 uses the reserved combo operand 7".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn no_solution_test() {
+        let input = Source::new(
+            "d17.txt",
+            "Register A: 729\nRegister B: 0\nRegister C: 0\n\nProgram: 0,1,5,4,3,0\n",
+        );
+        assert_eq!(
+            Day17.solve(&input),
+            Ok((
+                Answer::from("4,6,3,5,6,3,5,2,1,0"),
+                Answer::Unsolved("no value of A makes the program output 0,1,5,4,3,0".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn search_chunks_test() {
+        // each pass outputs the bottom two bits of a, so both 1 and 5 give the 1 before the final 2
+        let check: ChunkCheck = Box::new(|a, suffix| a % 4 == suffix[0] as usize);
+        let search = |is_quine: &dyn Fn(usize) -> bool| {
+            let mut tries_left = MAX_TRIES;
+            search_chunks(3, &check, is_quine, &[1, 2], 0, 0, &mut tries_left)
+        };
+        assert_eq!(search(&|_| true), Ok(Some(0o21)));
+        // a whole run that disagrees moves the search on to the next candidate
+        assert_eq!(search(&|a| a != 0o21), Ok(Some(0o25)));
+        assert_eq!(search(&|_| false), Ok(None));
+    }
+
+    #[test]
+    fn quine_search_test() {
+        let (start_state, prog) = d17_input();
+        assert_eq!(
+            quine_search(&start_state, &prog, &prog, MAX_TRIES),
+            Ok(258394985014171)
+        );
+        assert_eq!(
+            quine_search(&start_state, &prog, &prog, 3),
+            Err("gave up after 3 tries: that is the most allowed".to_string())
+        );
+
+        // the example from the puzzle text
+        let sample = vec![0, 3, 5, 4, 3, 0];
+        assert_eq!(
+            quine_search(&start_state, &sample, &sample, MAX_TRIES),
+            Ok(117440)
+        );
+
+        // out 1 can only ever output 1s
+        let ones = vec![0, 3, 5, 1, 3, 0];
+        assert_eq!(
+            quine_search(&start_state, &ones, &ones, MAX_TRIES),
+            Err("no value of A makes the program output 0,3,5,1,3,0".to_string())
+        );
+        assert_eq!(quine_search(&start_state, &ones, &[1, 1], MAX_TRIES), Ok(8));
+
+        assert!(quine_search(&start_state, &vec![5, 4], &[0], MAX_TRIES).is_err());
+
+        // the output is B ^ C, and analysis gives up on C because it could be left over from the
+        // pass before, so this one is searched by running it
+        let carried = vec![2, 4, 4, 0, 5, 5, 0, 3, 3, 0];
+        assert!(symbolic::analyse(&carried).is_err());
+        assert_eq!(
+            quine_search(&start_state, &carried, &[2, 4, 1], MAX_TRIES),
+            Ok(0o142)
         );
     }
 }
@@ -0,0 +1,224 @@
+// the other way from disasm: turning lines like `adv 3`, `out A` and `jnz start` into the numbers
+// of a program. Everything after a ';' is a comment, and `name:` at the start of a line labels the
+// instruction that follows, so the disassembler's own output assembles back into the same program
+
+use std::collections::HashMap;
+
+use super::{to_opcode, OpCode};
+use crate::utils::{Line, ParseError, Source};
+
+fn is_label(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|c: char| c.is_ascii_digit())
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+fn find_opcode(line: &Line, mnemonic: &str) -> Result<(u8, OpCode), ParseError> {
+    (0..8)
+        .map(|code| (code, to_opcode(code)))
+        .find(|(_, opcode)| opcode.mnemonic() == mnemonic)
+        .ok_or_else(|| {
+            line.error(
+                mnemonic,
+                "expected an instruction: adv, bxl, bst, jnz, bxc, out, bdv or cdv",
+            )
+        })
+}
+
+fn literal(line: &Line, operand: &str) -> Result<u8, ParseError> {
+    match operand.parse::<u8>() {
+        Ok(n) if n <= 7 => Ok(n),
+        _ => Err(line.error(operand, "expected a number from 0 to 7")),
+    }
+}
+
+fn combo(line: &Line, operand: &str) -> Result<u8, ParseError> {
+    match operand {
+        "A" => Ok(4),
+        "B" => Ok(5),
+        "C" => Ok(6),
+        "7" => Err(line.error(operand, "combo operand 7 is reserved")),
+        _ => match operand.parse::<u8>() {
+            Ok(n) if n <= 3 => Ok(n),
+            _ => Err(line.error(operand, "expected a combo operand: 0 to 3, A, B or C")),
+        },
+    }
+}
+
+// a jump goes to a label or straight to an address, either of which has to fit in 3 bits
+fn jump_target(
+    line: &Line,
+    operand: &str,
+    labels: &HashMap<&str, usize>,
+) -> Result<u8, ParseError> {
+    if !is_label(operand) {
+        return literal(line, operand);
+    }
+    match labels.get(operand) {
+        Some(&address) if address <= 7 => Ok(address as u8),
+        Some(&address) => Err(line.error(
+            operand,
+            format!(
+                "{} is at address {}, but jnz can only reach 0 to 7",
+                operand, address
+            ),
+        )),
+        None => Err(line.error(operand, "no such label")),
+    }
+}
+
+struct Statement<'a> {
+    line: Line<'a>,
+    mnemonic: &'a str,
+    operand: Option<&'a str>,
+}
+
+pub fn assemble(input: &Source) -> Result<Vec<u8>, ParseError> {
+    // first find every label, so that jumps can go forwards as well as back
+    let mut labels = HashMap::new();
+    let mut statements = vec![];
+    for line in input.lines() {
+        let code = line.text.split(';').next().unwrap_or("").trim();
+        let code = match code.split_once(':') {
+            Some((label, rest)) => {
+                let label = label.trim();
+                if !is_label(label) {
+                    return Err(line.error(label, "expected a label name"));
+                }
+                if labels.insert(label, 2 * statements.len()).is_some() {
+                    return Err(line.error(label, "label is already defined"));
+                }
+                rest.trim()
+            }
+            None => code,
+        };
+
+        let mut words = code.split_whitespace();
+        let Some(mnemonic) = words.next() else {
+            continue;
+        };
+        let operand = words.next();
+        if let Some(extra) = words.next() {
+            return Err(line.error(extra, "expected nothing after the operand"));
+        }
+        statements.push(Statement {
+            line,
+            mnemonic,
+            operand,
+        });
+    }
+
+    let mut prog = vec![];
+    for Statement {
+        line,
+        mnemonic,
+        operand,
+    } in &statements
+    {
+        let (code, opcode) = find_opcode(line, mnemonic)?;
+        let operand = match (opcode, *operand) {
+            // bxc ignores its operand, so it is optional
+            (OpCode::Bxc, None) => 0,
+            (_, None) => return Err(line.error("", format!("{} needs an operand", mnemonic))),
+            (OpCode::Jnz, Some(operand)) => jump_target(line, operand, &labels)?,
+            (opcode, Some(operand)) if opcode.takes_combo() => combo(line, operand)?,
+            (_, Some(operand)) => literal(line, operand)?,
+        };
+        prog.push(code);
+        prog.push(operand);
+    }
+    Ok(prog)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::d17::disasm::disassemble;
+    use crate::days::d17::vm::Machine;
+    use crate::days::d17::State;
+
+    fn assemble_text(text: &str) -> Result<Vec<u8>, String> {
+        assemble(&Source::new("test.asm", text)).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn assemble_test() {
+        let text = "\
+start:
+    adv 1    ; A = A >> 1
+    out A
+    jnz start
+";
+        let prog = assemble_text(text).unwrap();
+        assert_eq!(prog, vec![0, 1, 5, 4, 3, 0]);
+
+        // If register A contains 729, the program 0,1,5,4,3,0 would output 4,6,3,5,6,3,5,2,1,0.
+        let mut state = State::new(729, 0, 0);
+        Machine::new(&prog).run(&mut state).unwrap();
+        assert_eq!(state.output, vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+
+        // labels can come after the jump, and share a line with an instruction
+        assert_eq!(
+            assemble_text("jnz end\nbxc\nbxl 7\nend: out 3\n").unwrap(),
+            vec![3, 6, 4, 0, 1, 7, 5, 3]
+        );
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let prog = vec![2, 4, 1, 7, 7, 5, 0, 3, 4, 0, 1, 7, 5, 5, 3, 0];
+        let text = disassemble(&prog).join("\n");
+        assert_eq!(assemble_text(&text).unwrap(), prog);
+
+        // bxc ignores its operand, but it still has to come back the same
+        let prog = vec![4, 3, 5, 5, 4, 0];
+        let text = disassemble(&prog).join("\n");
+        assert!(text.contains("bxc 3"), "{}", text);
+        assert_eq!(assemble_text(&text).unwrap(), prog);
+    }
+
+    #[test]
+    fn assemble_errors_test() {
+        assert_eq!(
+            assemble_text("adv 1\nout 7\n").unwrap_err(),
+            "test.asm:2:5: combo operand 7 is reserved: '7'"
+        );
+        // 4 to 6 are registers, which have to be written as A, B and C
+        assert_eq!(
+            assemble_text("bst 4").unwrap_err(),
+            "test.asm:1:5: expected a combo operand: 0 to 3, A, B or C: '4'"
+        );
+        assert_eq!(
+            assemble_text("bxl 8").unwrap_err(),
+            "test.asm:1:5: expected a number from 0 to 7: '8'"
+        );
+        assert_eq!(
+            assemble_text("cdv D").unwrap_err(),
+            "test.asm:1:5: expected a combo operand: 0 to 3, A, B or C: 'D'"
+        );
+        assert_eq!(
+            assemble_text("\n  mul 3").unwrap_err(),
+            "test.asm:2:3: expected an instruction: adv, bxl, bst, jnz, bxc, out, bdv or cdv: 'mul'"
+        );
+        assert_eq!(
+            assemble_text("out").unwrap_err(),
+            "test.asm:1:4: out needs an operand: 'out'"
+        );
+        assert_eq!(
+            assemble_text("out A B").unwrap_err(),
+            "test.asm:1:7: expected nothing after the operand: 'B'"
+        );
+        assert_eq!(
+            assemble_text("jnz nowhere").unwrap_err(),
+            "test.asm:1:5: no such label: 'nowhere'"
+        );
+        assert_eq!(
+            assemble_text("a: bxc\na: bxc").unwrap_err(),
+            "test.asm:2:1: label is already defined: 'a'"
+        );
+        assert_eq!(
+            assemble_text("bxc\nbxc\nbxc\nbxc\nfar: bxc\njnz far").unwrap_err(),
+            "test.asm:6:5: far is at address 8, but jnz can only reach 0 to 7: 'far'"
+        );
+    }
+}
@@ -0,0 +1,264 @@
+// stepping through a program one instruction at a time: breakpoints on the instruction pointer or
+// on register values, an optional trace of the registers after every step, and a cap on the number
+// of steps so that a program that never halts can't hang whoever is running it
+
+use super::disasm;
+use super::vm::{Machine, DEFAULT_MAX_STEPS};
+use super::{to_opcode, State};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Register {
+    A,
+    B,
+    C,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Condition {
+    Equals(usize),
+    NotEquals(usize),
+    LessThan(usize),
+    GreaterThan(usize),
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Breakpoint {
+    // the instruction at this address is about to run
+    Address(usize),
+    Register(Register, Condition),
+}
+
+impl Breakpoint {
+    fn hit(&self, state: &State) -> bool {
+        match *self {
+            Breakpoint::Address(address) => state.inst_ptr == address,
+            Breakpoint::Register(register, condition) => {
+                let value = match register {
+                    Register::A => state.reg_a,
+                    Register::B => state.reg_b,
+                    Register::C => state.reg_c,
+                };
+                match condition {
+                    Condition::Equals(n) => value == n,
+                    Condition::NotEquals(n) => value != n,
+                    Condition::LessThan(n) => value < n,
+                    Condition::GreaterThan(n) => value > n,
+                }
+            }
+        }
+    }
+}
+
+// why run() stopped
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Stop {
+    Halted,
+    Breakpoint(Breakpoint),
+    // max_steps steps have run and the program is still going
+    DidNotHalt,
+    // a jump landed on an instruction with the reserved combo operand 7, at this address
+    Invalid(usize),
+}
+
+// one step of the trace: the instruction that ran and the registers after it
+#[derive(Clone, PartialEq, Debug)]
+pub struct TraceRow {
+    pub step: usize,
+    pub inst_ptr: usize,
+    pub instruction: String,
+    pub reg_a: usize,
+    pub reg_b: usize,
+    pub reg_c: usize,
+    pub output: Option<u8>,
+}
+
+pub struct Debugger<'a> {
+    pub state: State,
+    prog: &'a Vec<u8>,
+    machine: Machine,
+    pub breakpoints: Vec<Breakpoint>,
+    pub max_steps: usize,
+    // how many instructions have run so far
+    pub steps: usize,
+    // one row per step, only kept if this starts out as Some
+    pub trace: Option<Vec<TraceRow>>,
+    // whether the last run() stopped at a breakpoint and nothing has run since
+    at_breakpoint: bool,
+}
+
+impl<'a> Debugger<'a> {
+    pub fn new(state: State, prog: &'a Vec<u8>) -> Debugger<'a> {
+        Debugger {
+            state,
+            prog,
+            machine: Machine::new(prog),
+            breakpoints: vec![],
+            max_steps: DEFAULT_MAX_STEPS,
+            steps: 0,
+            trace: None,
+            at_breakpoint: false,
+        }
+    }
+
+    pub fn halted(&self) -> bool {
+        self.machine.halted(&self.state)
+    }
+
+    // run the next instruction, returning false if the program has already halted or the
+    // instruction is invalid
+    pub fn step(&mut self) -> bool {
+        if self.halted() {
+            return false;
+        }
+        let inst_ptr = self.state.inst_ptr;
+        let (opcode, operand) = (self.prog[inst_ptr], self.prog[inst_ptr + 1]);
+        let output_len = self.state.output.len();
+        if self.machine.step(&mut self.state).is_err() {
+            return false;
+        }
+        self.steps += 1;
+        self.at_breakpoint = false;
+
+        if let Some(trace) = &mut self.trace {
+            trace.push(TraceRow {
+                step: self.steps,
+                inst_ptr,
+                instruction: disasm::instruction(to_opcode(opcode), operand),
+                reg_a: self.state.reg_a,
+                reg_b: self.state.reg_b,
+                reg_c: self.state.reg_c,
+                output: self.state.output.get(output_len).copied(),
+            });
+        }
+        true
+    }
+
+    // run until the program halts, a breakpoint is hit or max_steps steps have run in total.
+    // Breakpoints are checked before each instruction runs, except that running again from a
+    // breakpoint moves on from it rather than stopping there straight away
+    pub fn run(&mut self) -> Stop {
+        let mut resuming = self.at_breakpoint;
+        loop {
+            if self.halted() {
+                return Stop::Halted;
+            }
+            if !resuming {
+                if let Some(breakpoint) = self.breakpoints.iter().find(|b| b.hit(&self.state)) {
+                    self.at_breakpoint = true;
+                    return Stop::Breakpoint(*breakpoint);
+                }
+            }
+            resuming = false;
+            if self.steps >= self.max_steps {
+                return Stop::DidNotHalt;
+            }
+            if !self.step() {
+                return Stop::Invalid(self.state.inst_ptr);
+            }
+        }
+    }
+
+    pub fn trace_table(&self) -> Vec<String> {
+        let mut lines = vec![format!(
+            "{:>5} {:>3}  {:<7} {:>16} {:>16} {:>16}  Out",
+            "Step", "IP", "Inst", "A", "B", "C"
+        )];
+        for row in self.trace.iter().flatten() {
+            lines.push(
+                format!(
+                    "{:>5} {:>3}  {:<7} {:>16} {:>16} {:>16}  {}",
+                    row.step,
+                    row.inst_ptr,
+                    row.instruction,
+                    row.reg_a,
+                    row.reg_b,
+                    row.reg_c,
+                    row.output.map(|o| o.to_string()).unwrap_or_default()
+                )
+                .trim_end()
+                .to_string(),
+            );
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_test() {
+        // If register A contains 729, the program 0,1,5,4,3,0 would output 4,6,3,5,6,3,5,2,1,0.
+        let prog = vec![0, 1, 5, 4, 3, 0];
+        let mut debugger = Debugger::new(State::new(729, 0, 0), &prog);
+        assert_eq!(debugger.run(), Stop::Halted);
+        assert_eq!(debugger.state.output, vec![4, 6, 3, 5, 6, 3, 5, 2, 1, 0]);
+        assert_eq!(debugger.steps, 30);
+        assert!(!debugger.step());
+
+        // jnz 0 with nothing to change A loops forever
+        let prog = vec![3, 0];
+        let mut debugger = Debugger::new(State::new(1, 0, 0), &prog);
+        debugger.max_steps = 100;
+        assert_eq!(debugger.run(), Stop::DidNotHalt);
+        assert_eq!(debugger.steps, 100);
+
+        // jnz 3 lands on 5,7, which is out with the reserved combo operand 7
+        let prog = vec![3, 3, 0, 5, 7, 0];
+        let mut debugger = Debugger::new(State::new(1, 0, 0), &prog);
+        assert_eq!(debugger.run(), Stop::Invalid(3));
+        assert_eq!(debugger.steps, 1);
+        assert!(!debugger.step());
+    }
+
+    #[test]
+    fn breakpoint_test() {
+        let prog = vec![0, 1, 5, 4, 3, 0];
+        let mut debugger = Debugger::new(State::new(729, 0, 0), &prog);
+        debugger.breakpoints = vec![
+            Breakpoint::Address(4),
+            Breakpoint::Register(Register::A, Condition::LessThan(100)),
+        ];
+
+        assert_eq!(debugger.run(), Stop::Breakpoint(Breakpoint::Address(4)));
+        assert_eq!((debugger.state.inst_ptr, debugger.steps), (4, 2));
+        assert_eq!(debugger.state.output, vec![4]);
+
+        // 729 / 2 / 2 / 2 = 91
+        assert_eq!(debugger.run(), Stop::Breakpoint(Breakpoint::Address(4)));
+        assert_eq!(
+            debugger.run(),
+            Stop::Breakpoint(Breakpoint::Register(Register::A, Condition::LessThan(100)))
+        );
+        assert_eq!(debugger.state.reg_a, 91);
+        assert_eq!(debugger.state.inst_ptr, 2);
+
+        // a breakpoint on the first instruction stops before anything runs
+        let mut debugger = Debugger::new(State::new(729, 0, 0), &prog);
+        debugger.breakpoints = vec![Breakpoint::Address(0)];
+        assert_eq!(debugger.run(), Stop::Breakpoint(Breakpoint::Address(0)));
+        assert_eq!((debugger.state.reg_a, debugger.steps), (729, 0));
+        assert_eq!(debugger.run(), Stop::Breakpoint(Breakpoint::Address(0)));
+        assert_eq!((debugger.state.reg_a, debugger.steps), (364, 3));
+    }
+
+    #[test]
+    fn trace_table_test() {
+        let prog = vec![0, 1, 5, 4, 3, 0];
+        let mut debugger = Debugger::new(State::new(4, 0, 0), &prog);
+        debugger.trace = Some(vec![]);
+        debugger.max_steps = 4;
+        assert_eq!(debugger.run(), Stop::DidNotHalt);
+        assert_eq!(
+            debugger.trace_table(),
+            vec![
+                " Step  IP  Inst                   A                B                C  Out",
+                "    1   0  adv 1                  2                0                0",
+                "    2   2  out A                  2                0                0  2",
+                "    3   4  jnz 0                  2                0                0",
+                "    4   0  adv 1                  1                0                0",
+            ]
+        );
+    }
+}
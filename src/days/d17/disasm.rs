@@ -0,0 +1,164 @@
+// turning a program back into something readable: one line per instruction with what it does to the
+// registers, and for the usual shape of program, its loop body as pseudo-code
+
+use std::collections::BTreeMap;
+
+use super::{combo_name, to_opcode, OpCode};
+
+fn label(address: usize) -> String {
+    format!("L{}", address)
+}
+
+// e.g. bst A, bxl 7 or bxc. bxc ignores its operand, so it is only shown when it isn't 0, which is
+// enough for the assembler to give back the same program
+pub(super) fn instruction(opcode: OpCode, operand: u8) -> String {
+    if opcode == OpCode::Bxc && operand == 0 {
+        opcode.mnemonic().to_string()
+    } else if opcode.takes_combo() {
+        format!("{} {}", opcode.mnemonic(), combo_name(operand))
+    } else {
+        format!("{} {}", opcode.mnemonic(), operand)
+    }
+}
+
+// what the instruction does, e.g. B = A % 8. Dividing by 2^n is the same as shifting right by n
+fn effect(opcode: OpCode, operand: u8, prog_len: usize) -> String {
+    if opcode.takes_combo() && operand > 6 {
+        return "invalid: combo operand 7 is reserved".to_string();
+    }
+    let combo = combo_name(operand);
+    match opcode {
+        OpCode::Adv => format!("A = A >> {}", combo),
+        OpCode::Bdv => format!("B = A >> {}", combo),
+        OpCode::Cdv => format!("C = A >> {}", combo),
+        OpCode::Bxl => format!("B = B ^ {}", operand),
+        OpCode::Bst => format!("B = {} % 8", combo),
+        OpCode::Jnz if operand as usize >= prog_len => "if A != 0 halt".to_string(),
+        OpCode::Jnz if operand % 2 == 1 => {
+            format!("if A != 0 jump to {}, between instructions", operand)
+        }
+        OpCode::Jnz => format!("if A != 0 jump to {}", label(operand as usize)),
+        OpCode::Bxc => "B = B ^ C".to_string(),
+        OpCode::Out => format!("output {} % 8", combo),
+    }
+}
+
+fn instructions(prog: &Vec<u8>) -> impl Iterator<Item = (usize, OpCode, u8)> + '_ {
+    prog.chunks_exact(2)
+        .enumerate()
+        .map(|(idx, pair)| (2 * idx, to_opcode(pair[0]), pair[1]))
+}
+
+// the instructions each jump lands on, and the addresses of the jumps that go there
+fn jump_targets(prog: &Vec<u8>) -> BTreeMap<usize, Vec<usize>> {
+    let mut targets: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (address, opcode, operand) in instructions(prog) {
+        let target = operand as usize;
+        if opcode == OpCode::Jnz && target < prog.len() && target.is_multiple_of(2) {
+            targets.entry(target).or_default().push(address);
+        }
+    }
+    targets
+}
+
+// one line per instruction, with a label line before each jump target, e.g.
+// L0:  ; from 14
+//     bst A   ;  0: B = A % 8
+pub fn disassemble(prog: &Vec<u8>) -> Vec<String> {
+    let targets = jump_targets(prog);
+    let mut lines = vec![];
+    for (address, opcode, operand) in instructions(prog) {
+        if let Some(sources) = targets.get(&address) {
+            let sources: Vec<String> = sources.iter().map(|s| s.to_string()).collect();
+            lines.push(format!(
+                "{}:  ; from {}",
+                label(address),
+                sources.join(", ")
+            ));
+        }
+        lines.push(format!(
+            "    {:<7} ; {:>2}: {}",
+            instruction(opcode, operand),
+            address,
+            effect(opcode, operand, prog.len())
+        ));
+    }
+    lines
+}
+
+// programs are usually a loop body followed by a jump back to the start while A is not zero, which
+// reads as a do-while loop. None for anything else
+pub fn pseudo_code(prog: &Vec<u8>) -> Option<Vec<String>> {
+    let body: Vec<(usize, OpCode, u8)> = instructions(prog).collect();
+    let (last, body) = body.split_last()?;
+    if *last != (prog.len() - 2, OpCode::Jnz, 0)
+        || body.iter().any(|(_, opcode, _)| *opcode == OpCode::Jnz)
+    {
+        return None;
+    }
+
+    let mut lines = vec!["do {".to_string()];
+    for (_, opcode, operand) in body {
+        lines.push(format!("    {}", effect(*opcode, *operand, prog.len())));
+    }
+    lines.push("} while A != 0".to_string());
+    Some(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROG: [u8; 16] = [2, 4, 1, 7, 7, 5, 0, 3, 4, 0, 1, 7, 5, 5, 3, 0];
+
+    #[test]
+    fn disassemble_test() {
+        assert_eq!(
+            disassemble(&PROG.to_vec()),
+            vec![
+                "L0:  ; from 14",
+                "    bst A   ;  0: B = A % 8",
+                "    bxl 7   ;  2: B = B ^ 7",
+                "    cdv B   ;  4: C = A >> B",
+                "    adv 3   ;  6: A = A >> 3",
+                "    bxc     ;  8: B = B ^ C",
+                "    bxl 7   ; 10: B = B ^ 7",
+                "    out B   ; 12: output B % 8",
+                "    jnz 0   ; 14: if A != 0 jump to L0",
+            ]
+        );
+
+        assert_eq!(
+            disassemble(&vec![5, 7, 3, 1, 3, 8]),
+            vec![
+                "    out ?   ;  0: invalid: combo operand 7 is reserved",
+                "    jnz 1   ;  2: if A != 0 jump to 1, between instructions",
+                "    jnz 8   ;  4: if A != 0 halt",
+            ]
+        );
+    }
+
+    #[test]
+    fn pseudo_code_test() {
+        assert_eq!(
+            pseudo_code(&PROG.to_vec()).unwrap(),
+            vec![
+                "do {",
+                "    B = A % 8",
+                "    B = B ^ 7",
+                "    C = A >> B",
+                "    A = A >> 3",
+                "    B = B ^ C",
+                "    B = B ^ 7",
+                "    output B % 8",
+                "} while A != 0",
+            ]
+        );
+
+        // no loop, a loop that doesn't go back to the start, and more than one jump
+        assert_eq!(pseudo_code(&vec![5, 4]), None);
+        assert_eq!(pseudo_code(&vec![5, 4, 0, 1, 3, 2]), None);
+        assert_eq!(pseudo_code(&vec![3, 0, 5, 4, 3, 0]), None);
+        assert_eq!(pseudo_code(&vec![]), None);
+    }
+}
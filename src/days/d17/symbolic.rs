@@ -0,0 +1,395 @@
+// running one pass of a program's loop with A left unknown, to see what each output is made of.
+// Programs are a loop that shifts A right a few bits and outputs once per pass, so pass i sees
+// a = A >> (shift * i) and outputs some f(a). Knowing f and which bits of a it reads says whether A
+// can be worked out a chunk at a time from the top, and lets the quine search check a chunk by
+// working out f directly instead of running the whole program
+
+use std::collections::BTreeSet;
+use std::fmt::{self, Display};
+
+use super::vm::divide;
+use super::{to_opcode, OpCode};
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Expr {
+    Const(usize),
+    // A at the start of the pass
+    A,
+    // B and C as the pass before left them
+    InB,
+    InC,
+    // x >> y, which is how adv, bdv and cdv divide by 2^y
+    Shr(Box<Expr>, Box<Expr>),
+    Mod8(Box<Expr>),
+    Xor(Vec<Expr>),
+}
+
+fn shr(x: Expr, y: Expr) -> Expr {
+    match (x, y) {
+        (x, Expr::Const(0)) => x,
+        (Expr::Const(x), Expr::Const(y)) => Expr::Const(divide(x, y)),
+        // two shifts by constants are one bigger shift
+        (Expr::Shr(x, inner), Expr::Const(y)) => match *inner {
+            Expr::Const(inner) => Expr::Shr(x, Box::new(Expr::Const(inner + y))),
+            inner => Expr::Shr(
+                Box::new(Expr::Shr(x, Box::new(inner))),
+                Box::new(Expr::Const(y)),
+            ),
+        },
+        (x, y) => Expr::Shr(Box::new(x), Box::new(y)),
+    }
+}
+
+fn mod8(x: Expr) -> Expr {
+    match x {
+        Expr::Const(n) => Expr::Const(n % 8),
+        Expr::Mod8(_) => x,
+        x => Expr::Mod8(Box::new(x)),
+    }
+}
+
+// the constants are combined into one at the end, so x ^ 7 ^ 7 is just x
+fn xor(x: Expr, y: Expr) -> Expr {
+    let mut terms = vec![];
+    let mut constant = 0;
+    for term in [x, y] {
+        let parts = match term {
+            Expr::Xor(parts) => parts,
+            term => vec![term],
+        };
+        for part in parts {
+            match part {
+                Expr::Const(n) => constant ^= n,
+                part => terms.push(part),
+            }
+        }
+    }
+    if constant != 0 || terms.is_empty() {
+        terms.push(Expr::Const(constant));
+    }
+    if terms.len() == 1 {
+        terms.pop().unwrap()
+    } else {
+        Expr::Xor(terms)
+    }
+}
+
+impl Expr {
+    // the largest value the expression can have, or None if it can be anything
+    fn max_value(&self) -> Option<usize> {
+        match self {
+            Expr::Const(n) => Some(*n),
+            Expr::A | Expr::InB | Expr::InC => None,
+            Expr::Shr(x, _) => x.max_value(),
+            Expr::Mod8(x) => Some(x.max_value().unwrap_or(7).min(7)),
+            Expr::Xor(terms) => terms
+                .iter()
+                .map(Expr::max_value)
+                .try_fold(0, |acc, max| max.map(|m| acc | m))
+                .map(|bits| match bits {
+                    0 => 0,
+                    bits => usize::MAX >> bits.leading_zeros(),
+                }),
+        }
+    }
+
+    // which bits of a the given bits of this expression can depend on, or None if there's no limit
+    fn depends_on(&self, bits: &BTreeSet<usize>) -> Option<BTreeSet<usize>> {
+        match self {
+            Expr::Const(_) | Expr::InB | Expr::InC => Some(BTreeSet::new()),
+            Expr::A => Some(bits.clone()),
+            Expr::Mod8(x) => x.depends_on(&bits.iter().copied().filter(|&b| b < 3).collect()),
+            Expr::Xor(terms) => terms.iter().try_fold(BTreeSet::new(), |mut acc, term| {
+                acc.extend(term.depends_on(bits)?);
+                Some(acc)
+            }),
+            // bit j of x >> y is bit j + y of x, for every y it could be
+            Expr::Shr(x, y) => {
+                let max_shift = y.max_value()?;
+                let shifts: Vec<usize> = match **y {
+                    Expr::Const(shift) => vec![shift],
+                    _ => (0..=max_shift).collect(),
+                };
+                let shifted: BTreeSet<usize> = bits
+                    .iter()
+                    .flat_map(|&b| shifts.iter().map(move |s| b + s))
+                    .collect();
+                let shift_bits = (0..usize::BITS - max_shift.leading_zeros())
+                    .map(|b| b as usize)
+                    .collect();
+                let mut deps = x.depends_on(&shifted)?;
+                deps.extend(y.depends_on(&shift_bits)?);
+                Some(deps)
+            }
+        }
+    }
+
+    // work out the expression for a known a
+    pub fn eval(&self, a: usize) -> usize {
+        match self {
+            Expr::Const(n) => *n,
+            Expr::A => a,
+            Expr::InB | Expr::InC => panic!("B and C from the pass before are unknown"),
+            Expr::Shr(x, y) => divide(x.eval(a), y.eval(a)),
+            Expr::Mod8(x) => x.eval(a) % 8,
+            Expr::Xor(terms) => terms.iter().fold(0, |acc, term| acc ^ term.eval(a)),
+        }
+    }
+
+    // whether it uses B or C from the pass before
+    fn uses_carried(&self) -> bool {
+        match self {
+            Expr::InB | Expr::InC => true,
+            Expr::Const(_) | Expr::A => false,
+            Expr::Shr(x, y) => x.uses_carried() || y.uses_carried(),
+            Expr::Mod8(x) => x.uses_carried(),
+            Expr::Xor(terms) => terms.iter().any(Expr::uses_carried),
+        }
+    }
+
+    fn is_atom(&self) -> bool {
+        matches!(self, Expr::Const(_) | Expr::A | Expr::InB | Expr::InC)
+    }
+
+    fn fmt_inner(&self) -> String {
+        if self.is_atom() {
+            self.to_string()
+        } else {
+            format!("({})", self)
+        }
+    }
+}
+
+impl Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Const(n) => write!(f, "{}", n),
+            Expr::A => write!(f, "a"),
+            Expr::InB => write!(f, "b"),
+            Expr::InC => write!(f, "c"),
+            Expr::Shr(x, y) => write!(f, "{} >> {}", x.fmt_inner(), y.fmt_inner()),
+            Expr::Mod8(x) => write!(f, "{} % 8", x.fmt_inner()),
+            Expr::Xor(terms) => {
+                let terms: Vec<String> = terms.iter().map(Expr::fmt_inner).collect();
+                write!(f, "{}", terms.join(" ^ "))
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Analysis {
+    // how many bits A is shifted right by on each pass
+    pub shift: usize,
+    // what each pass outputs, in terms of a, A at the start of the pass
+    pub output: Expr,
+    // the bits of a that the output depends on, None if it can depend on any bit from some point up
+    pub depends_on: Option<BTreeSet<usize>>,
+}
+
+fn combo(operand: u8, a: &Expr, b: &Expr, c: &Expr) -> Result<Expr, String> {
+    match operand {
+        0..=3 => Ok(Expr::Const(operand as usize)),
+        4 => Ok(a.clone()),
+        5 => Ok(b.clone()),
+        6 => Ok(c.clone()),
+        _ => Err("combo operand 7 is reserved".to_string()),
+    }
+}
+
+// only programs that are one loop, shifting A right by a constant and outputting once per pass,
+// with nothing carried over from one pass to the next but A, can be analysed
+pub fn analyse(prog: &Vec<u8>) -> Result<Analysis, String> {
+    let instructions: Vec<(OpCode, u8)> = prog
+        .chunks_exact(2)
+        .map(|inst| (to_opcode(inst[0]), inst[1]))
+        .collect();
+    let Some((&(OpCode::Jnz, 0), body)) = instructions.split_last() else {
+        return Err("the program doesn't end by jumping back to the start".to_string());
+    };
+
+    let (mut a, mut b, mut c) = (Expr::A, Expr::InB, Expr::InC);
+    let mut outputs = vec![];
+    for &(opcode, operand) in body {
+        match opcode {
+            OpCode::Adv => a = shr(a.clone(), combo(operand, &a, &b, &c)?),
+            OpCode::Bdv => b = shr(a.clone(), combo(operand, &a, &b, &c)?),
+            OpCode::Cdv => c = shr(a.clone(), combo(operand, &a, &b, &c)?),
+            OpCode::Bxl => b = xor(b, Expr::Const(operand as usize)),
+            OpCode::Bst => b = mod8(combo(operand, &a, &b, &c)?),
+            OpCode::Jnz => return Err("the loop has a jump inside it".to_string()),
+            OpCode::Bxc => b = xor(b, c.clone()),
+            OpCode::Out => outputs.push(mod8(combo(operand, &a, &b, &c)?)),
+        }
+    }
+
+    let shift = match a {
+        Expr::Shr(x, y) if *x == Expr::A => match *y {
+            Expr::Const(shift) if shift < 32 => shift,
+            _ => return Err("A isn't shifted right by a small constant on each pass".to_string()),
+        },
+        _ => return Err("A isn't shifted right by a small constant on each pass".to_string()),
+    };
+    let [output] = &outputs[..] else {
+        return Err(format!(
+            "each pass outputs {} times rather than once",
+            outputs.len()
+        ));
+    };
+    let output = output.clone();
+    if output.uses_carried() {
+        return Err(format!(
+            "the output {} depends on B or C from the pass before",
+            output
+        ));
+    }
+
+    let depends_on = output.depends_on(&(0..3).collect());
+    Ok(Analysis {
+        shift,
+        output,
+        depends_on,
+    })
+}
+
+// e.g. "bits 0 to 9" or "bits 0 to 2 and 5"
+fn describe_bits(bits: &BTreeSet<usize>, offset: usize) -> String {
+    let mut ranges: Vec<(usize, usize)> = vec![];
+    for &bit in bits {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == bit => *end = bit,
+            _ => ranges.push((bit, bit)),
+        }
+    }
+    let ranges: Vec<String> = ranges
+        .iter()
+        .map(|&(start, end)| {
+            if start == end {
+                (start + offset).to_string()
+            } else {
+                format!("{} to {}", start + offset, end + offset)
+            }
+        })
+        .collect();
+    match ranges.len() {
+        0 => "no bits".to_string(),
+        1 if bits.len() == 1 => format!("bit {}", ranges[0]),
+        _ => format!("bits {}", ranges.join(" and ")),
+    }
+}
+
+// what the analysis says about a program producing `outputs` outputs, one line at a time
+pub fn report(analysis: &Analysis, outputs: usize) -> Vec<String> {
+    let shift = analysis.shift;
+    let mut lines = vec![
+        format!(
+            "each pass shifts A right by {} and outputs once, so pass i starts with a = A >> {}i",
+            shift, shift
+        ),
+        format!("output i = {}", analysis.output),
+    ];
+    let Some(bits) = &analysis.depends_on else {
+        lines.push(format!(
+            "which can depend on any bit of a, so output i can depend on any bit of A from bit {}i up",
+            shift
+        ));
+        return lines;
+    };
+    lines.push(format!("which depends on {} of a", describe_bits(bits, 0)));
+    for i in 0..outputs {
+        lines.push(format!(
+            "output {:>2}: {} of A",
+            i,
+            describe_bits(bits, shift * i)
+        ));
+    }
+    lines.push(format!(
+        "no output i depends on bits of A below {}i, so A can be found {} bits at a time from the top, \
+         starting with the last output",
+        shift, shift
+    ));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROG: [u8; 16] = [2, 4, 1, 7, 7, 5, 0, 3, 4, 0, 1, 7, 5, 5, 3, 0];
+
+    #[test]
+    fn analyse_test() {
+        let analysis = analyse(&PROG.to_vec()).unwrap();
+        assert_eq!(analysis.shift, 3);
+        assert_eq!(
+            analysis.output.to_string(),
+            "((a % 8) ^ (a >> ((a % 8) ^ 7))) % 8"
+        );
+        assert_eq!(analysis.depends_on, Some((0..10).collect()));
+        // 258394985014171 is the quine, so its top chunk 7 outputs the last value, 0
+        assert_eq!(analysis.output.eval(7), 0);
+        assert_eq!(analysis.output.eval(258394985014171), 2);
+
+        // the example from the puzzle text
+        let analysis = analyse(&vec![0, 3, 5, 4, 3, 0]).unwrap();
+        assert_eq!(analysis.shift, 3);
+        assert_eq!(analysis.output.to_string(), "(a >> 3) % 8");
+        assert_eq!(analysis.depends_on, Some((3..6).collect()));
+
+        // two shifts by 1 are one shift by 2
+        let analysis = analyse(&vec![0, 1, 5, 4, 0, 1, 3, 0]).unwrap();
+        assert_eq!(analysis.shift, 2);
+        assert_eq!(analysis.output.to_string(), "(a >> 1) % 8");
+
+        // shifting by a register has no limit on the bits it reads
+        let analysis = analyse(&vec![2, 4, 7, 5, 5, 6, 0, 3, 3, 0]).unwrap();
+        assert_eq!(analysis.output.to_string(), "(a >> (a % 8)) % 8");
+        assert_eq!(analysis.depends_on, Some((0..10).collect()));
+        let analysis = analyse(&vec![7, 4, 5, 6, 0, 3, 3, 0]).unwrap();
+        assert_eq!(analysis.depends_on, None);
+    }
+
+    #[test]
+    fn analyse_errors_test() {
+        let error = |prog: Vec<u8>| analyse(&prog).unwrap_err();
+        assert_eq!(
+            error(vec![5, 4]),
+            "the program doesn't end by jumping back to the start"
+        );
+        assert_eq!(
+            error(vec![0, 4, 5, 4, 3, 0]),
+            "A isn't shifted right by a small constant on each pass"
+        );
+        assert_eq!(
+            error(vec![0, 3, 5, 4, 5, 4, 3, 0]),
+            "each pass outputs 2 times rather than once"
+        );
+        assert_eq!(
+            error(vec![0, 3, 1, 1, 5, 5, 3, 0]),
+            "the output (b ^ 1) % 8 depends on B or C from the pass before"
+        );
+        assert_eq!(error(vec![0, 3, 5, 7, 3, 0]), "combo operand 7 is reserved");
+    }
+
+    #[test]
+    fn report_test() {
+        let analysis = analyse(&PROG.to_vec()).unwrap();
+        let report = report(&analysis, 16);
+        assert_eq!(
+            report[..5],
+            [
+                "each pass shifts A right by 3 and outputs once, so pass i starts with a = A >> 3i",
+                "output i = ((a % 8) ^ (a >> ((a % 8) ^ 7))) % 8",
+                "which depends on bits 0 to 9 of a",
+                "output  0: bits 0 to 9 of A",
+                "output  1: bits 3 to 12 of A",
+            ]
+        );
+        assert_eq!(report[18], "output 15: bits 45 to 54 of A");
+        assert_eq!(report.len(), 20);
+
+        let bits = BTreeSet::from([0, 1, 2, 5]);
+        assert_eq!(describe_bits(&bits, 3), "bits 3 to 5 and 8");
+        assert_eq!(describe_bits(&BTreeSet::from([4]), 0), "bit 4");
+    }
+}
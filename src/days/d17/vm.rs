@@ -0,0 +1,234 @@
+// running a program: it is decoded once up front, and running it changes the registers in place
+// rather than building a new State for every instruction
+
+use super::{to_opcode, OpCode, State};
+
+// enough for any program that halts in reasonable time
+pub const DEFAULT_MAX_STEPS: usize = 1_000_000;
+
+// a combo operand, already worked out to the register or number it reads
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Combo {
+    Literal(usize),
+    A,
+    B,
+    C,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Instruction {
+    Adv(Combo),
+    Bdv(Combo),
+    Cdv(Combo),
+    Bxl(usize),
+    Bst(Combo),
+    Jnz(usize),
+    Bxc,
+    Out(Combo),
+}
+
+// None for combo operand 7, which is reserved
+fn decode_combo(operand: u8) -> Option<Combo> {
+    match operand {
+        0..=3 => Some(Combo::Literal(operand as usize)),
+        4 => Some(Combo::A),
+        5 => Some(Combo::B),
+        6 => Some(Combo::C),
+        _ => None,
+    }
+}
+
+fn decode(opcode: u8, operand: u8) -> Option<Instruction> {
+    let combo = decode_combo(operand);
+    Some(match to_opcode(opcode) {
+        OpCode::Adv => Instruction::Adv(combo?),
+        OpCode::Bdv => Instruction::Bdv(combo?),
+        OpCode::Cdv => Instruction::Cdv(combo?),
+        OpCode::Bxl => Instruction::Bxl(operand as usize),
+        OpCode::Bst => Instruction::Bst(combo?),
+        OpCode::Jnz => Instruction::Jnz(operand as usize),
+        OpCode::Bxc => Instruction::Bxc,
+        OpCode::Out => Instruction::Out(combo?),
+    })
+}
+
+fn value(state: &State, combo: Combo) -> usize {
+    match combo {
+        Combo::Literal(n) => n,
+        Combo::A => state.reg_a,
+        Combo::B => state.reg_b,
+        Combo::C => state.reg_c,
+    }
+}
+
+// A / 2^n, which is 0 once n is at least the width of A
+pub(super) fn divide(reg_a: usize, n: usize) -> usize {
+    reg_a
+        .checked_shr(n.try_into().unwrap_or(u32::MAX))
+        .unwrap_or(0)
+}
+
+pub struct Machine {
+    // the instruction starting at each address. A jump can land on an odd address, so every address
+    // is decoded, not just the even ones. None where combo operand 7 is used
+    decoded: Vec<Option<Instruction>>,
+    // run() and run_matching() give up on a program once it has run this many instructions
+    pub max_steps: usize,
+}
+
+impl Machine {
+    pub fn new(prog: &Vec<u8>) -> Machine {
+        Machine {
+            decoded: prog
+                .windows(2)
+                .map(|pair| decode(pair[0], pair[1]))
+                .collect(),
+            max_steps: DEFAULT_MAX_STEPS,
+        }
+    }
+
+    // the program halts when the instruction pointer no longer points at a whole instruction
+    pub fn halted(&self, state: &State) -> bool {
+        state.inst_ptr >= self.decoded.len()
+    }
+
+    // run the instruction at the instruction pointer, which must not have halted. Parsing rejects
+    // combo operand 7, but a jump to an odd address can still land on one, and then nothing runs
+    pub fn step(&self, state: &mut State) -> Result<(), String> {
+        let instruction = self.decoded[state.inst_ptr].ok_or(format!(
+            "the instruction at address {} uses the reserved combo operand 7",
+            state.inst_ptr
+        ))?;
+        state.inst_ptr += 2;
+        match instruction {
+            Instruction::Adv(combo) => state.reg_a = divide(state.reg_a, value(state, combo)),
+            Instruction::Bdv(combo) => state.reg_b = divide(state.reg_a, value(state, combo)),
+            Instruction::Cdv(combo) => state.reg_c = divide(state.reg_a, value(state, combo)),
+            Instruction::Bxl(n) => state.reg_b ^= n,
+            Instruction::Bst(combo) => state.reg_b = value(state, combo) % 8,
+            Instruction::Jnz(address) => {
+                if state.reg_a != 0 {
+                    state.inst_ptr = address;
+                }
+            }
+            Instruction::Bxc => state.reg_b ^= state.reg_c,
+            Instruction::Out(combo) => state.output.push((value(state, combo) % 8) as u8),
+        }
+        Ok(())
+    }
+
+    // run until the program halts, or say why it didn't: an invalid instruction, or still running
+    // after max_steps instructions
+    pub fn run(&self, state: &mut State) -> Result<(), String> {
+        for _ in 0..self.max_steps {
+            if self.halted(state) {
+                return Ok(());
+            }
+            self.step(state)?;
+        }
+        if self.halted(state) {
+            Ok(())
+        } else {
+            Err(format!(
+                "the program did not halt within {} steps",
+                self.max_steps
+            ))
+        }
+    }
+
+    // whether the program outputs exactly target and halts within max_steps instructions, stopping
+    // at the first output that doesn't match or an invalid instruction
+    pub fn run_matching(&self, state: &mut State, target: &[u8]) -> bool {
+        for _ in 0..self.max_steps {
+            if self.halted(state) {
+                return state.output.len() == target.len();
+            }
+            let output_len = state.output.len();
+            if self.step(state).is_err() {
+                return false;
+            }
+            if let Some(out) = state.output.get(output_len) {
+                if target.get(output_len) != Some(out) {
+                    return false;
+                }
+            }
+        }
+        self.halted(state) && state.output.len() == target.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_test() {
+        assert_eq!(decode(2, 4), Some(Instruction::Bst(Combo::A)));
+        assert_eq!(decode(1, 7), Some(Instruction::Bxl(7)));
+        assert_eq!(decode(0, 3), Some(Instruction::Adv(Combo::Literal(3))));
+        assert_eq!(decode(4, 7), Some(Instruction::Bxc));
+        assert_eq!(decode(5, 7), None);
+    }
+
+    #[test]
+    fn run_test() {
+        let prog = vec![2, 4, 1, 7, 7, 5, 0, 3, 4, 0, 1, 7, 5, 5, 3, 0];
+        let machine = Machine::new(&prog);
+        for reg_a in [0, 1, 7, 729, 2024, 62769524, 258394985014171] {
+            let mut run = State::new(reg_a, 0, 0);
+            assert_eq!(machine.run(&mut run), Ok(()));
+
+            let mut stepped = State::new(reg_a, 0, 0);
+            while !machine.halted(&stepped) {
+                machine.step(&mut stepped).unwrap();
+            }
+            assert_eq!(run, stepped);
+        }
+
+        // adv A shifts by far more than the width of A
+        let mut big_shift = State::new(729, 0, 0);
+        assert_eq!(Machine::new(&vec![0, 4, 5, 4]).run(&mut big_shift), Ok(()));
+        assert_eq!((big_shift.reg_a, big_shift.output), (0, vec![0]));
+
+        // jnz 0 with nothing to change A loops forever
+        let mut machine = Machine::new(&vec![3, 0]);
+        machine.max_steps = 100;
+        let mut forever = State::new(1, 0, 0);
+        assert_eq!(
+            machine.run(&mut forever),
+            Err("the program did not halt within 100 steps".to_string())
+        );
+        assert!(!machine.run_matching(&mut State::new(1, 0, 0), &[]));
+        assert_eq!(forever.inst_ptr, 0);
+
+        // jnz 3 lands between instructions, on 5,7: out with the reserved combo operand 7
+        let machine = Machine::new(&vec![3, 3, 0, 5, 7, 0]);
+        let mut invalid = State::new(1, 0, 0);
+        assert_eq!(
+            machine.run(&mut invalid),
+            Err("the instruction at address 3 uses the reserved combo operand 7".to_string())
+        );
+        assert_eq!(invalid.inst_ptr, 3);
+        assert!(!machine.run_matching(&mut State::new(1, 0, 0), &[]));
+    }
+
+    #[test]
+    fn run_matching_test() {
+        // If register A contains 729, the program 0,1,5,4,3,0 would output 4,6,3,5,6,3,5,2,1,0.
+        let machine = Machine::new(&vec![0, 1, 5, 4, 3, 0]);
+        let target = [4, 6, 3, 5, 6, 3, 5, 2, 1, 0];
+        assert!(machine.run_matching(&mut State::new(729, 0, 0), &target));
+
+        // stops at the first output that doesn't match
+        let mut early = State::new(729, 0, 0);
+        assert!(!machine.run_matching(&mut early, &[4, 6, 4]));
+        assert_eq!(early.output, vec![4, 6, 3]);
+
+        // more output than the target, and less
+        assert!(!machine.run_matching(&mut State::new(729, 0, 0), &target[..9]));
+        assert!(!machine.run_matching(
+            &mut State::new(729, 0, 0),
+            &[4, 6, 3, 5, 6, 3, 5, 2, 1, 0, 0]
+        ));
+    }
+}
@@ -0,0 +1,247 @@
+use crate::solution::{Answer, Solution};
+use crate::utils::matrix;
+use crate::utils::plane::{self, Direction};
+use crate::utils::{ParseError, Source};
+use log::debug;
+use std::collections::VecDeque;
+
+const CORRUPTED: char = '#';
+const SAFE: char = '.';
+
+// the memory space is size x size, and part 1 looks at it once `fallen` bytes have fallen
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Space {
+    size: usize,
+    fallen: usize,
+}
+
+const REAL: Space = Space {
+    size: 71,
+    fallen: 1024,
+};
+const SAMPLE: Space = Space {
+    size: 7,
+    fallen: 12,
+};
+
+pub struct Memory {
+    // where each byte falls, as (x, y), in the order they fall
+    bytes: Vec<(usize, usize)>,
+    space: Space,
+}
+
+// parse lines like 5,4 into (5, 4), each of which has to be inside the real memory space
+fn parse(input: &Source) -> Result<Vec<(usize, usize)>, ParseError> {
+    let size = REAL.size;
+    input
+        .lines()
+        .map(|line| {
+            let (x, y) = line
+                .text
+                .split_once(',')
+                .ok_or_else(|| line.error("", "expected x,y"))?;
+            let what = format!("a coordinate from 0 to {}", size - 1);
+            let coord: (usize, usize) = (line.parse(x, &what)?, line.parse(y, &what)?);
+            for (part, value) in [(x, coord.0), (y, coord.1)] {
+                if value >= size {
+                    return Err(line.error(part, format!("expected {}", what)));
+                }
+            }
+            Ok(coord)
+        })
+        .collect()
+}
+
+// the sample uses a smaller memory space than the real input, which every one of its bytes falls inside
+fn space(bytes: &[(usize, usize)]) -> Space {
+    if bytes
+        .iter()
+        .all(|&(x, y)| x < SAMPLE.size && y < SAMPLE.size)
+    {
+        SAMPLE
+    } else {
+        REAL
+    }
+}
+
+// the memory space once the given bytes have fallen, indexed by row (y) and then column (x)
+fn corrupt(bytes: &[(usize, usize)], size: usize) -> Vec<Vec<char>> {
+    let mut grid = vec![vec![SAFE; size]; size];
+    for &(x, y) in bytes {
+        grid[y][x] = CORRUPTED;
+    }
+    grid
+}
+
+// the fewest steps from the top left corner to the bottom right, if it can be reached at all
+fn shortest_path(grid: &Vec<Vec<char>>) -> Option<usize> {
+    let (size, _) = matrix::dimensions(grid);
+    if grid[0][0] == CORRUPTED {
+        return None;
+    }
+
+    // breadth first, so the first time a cell is reached is the shortest way there
+    let mut steps = vec![vec![None; size]; size];
+    steps[0][0] = Some(0);
+    let mut queue = VecDeque::from([(0, 0)]);
+    while let Some((row, col)) = queue.pop_front() {
+        let steps_here = steps[row][col]?;
+        if (row, col) == (size - 1, size - 1) {
+            return Some(steps_here);
+        }
+        for dir in Direction::all() {
+            let next = plane::move_one(row, col, size, size, dir);
+            if !next.out_of_bounds
+                && grid[next.row][next.col] == SAFE
+                && steps[next.row][next.col].is_none()
+            {
+                steps[next.row][next.col] = Some(steps_here + 1);
+                queue.push_back((next.row, next.col));
+            }
+        }
+    }
+    None
+}
+
+fn exit_reachable(memory: &Memory, fallen: usize) -> bool {
+    shortest_path(&corrupt(&memory.bytes[..fallen], memory.space.size)).is_some()
+}
+
+pub fn d18p1(memory: &Memory) -> Result<usize, String> {
+    let fallen = memory.space.fallen.min(memory.bytes.len());
+    shortest_path(&corrupt(&memory.bytes[..fallen], memory.space.size)).ok_or(format!(
+        "the exit is already cut off after {} bytes",
+        fallen
+    ))
+}
+
+// the first byte to cut off the exit. Once it is cut off it stays that way, so a binary search over how
+// many bytes have fallen finds it with a handful of searches rather than one per byte
+pub fn d18p2(memory: &Memory) -> Result<(usize, usize), String> {
+    let (mut reachable, mut cut_off) = (0, memory.bytes.len());
+    if exit_reachable(memory, cut_off) {
+        return Err("the exit is never cut off".to_string());
+    }
+    while cut_off - reachable > 1 {
+        let fallen = (reachable + cut_off) / 2;
+        let reached = exit_reachable(memory, fallen);
+        debug!("after {} bytes the exit is reachable: {}", fallen, reached);
+        if reached {
+            reachable = fallen;
+        } else {
+            cut_off = fallen;
+        }
+    }
+    Ok(memory.bytes[cut_off - 1])
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Memory;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        let bytes = parse(input)?;
+        let space = space(&bytes);
+        Ok(Memory { bytes, space })
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        match d18p1(input) {
+            Ok(steps) => steps.into(),
+            Err(reason) => Answer::Unsolved(reason),
+        }
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        match d18p2(input) {
+            Ok((x, y)) => format!("{},{}", x, y).into(),
+            Err(reason) => Answer::Unsolved(reason),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solver;
+
+    const SAMPLE_BYTES: &str = "\
+5,4\n4,2\n4,5\n3,0\n2,1\n6,3\n2,4\n1,5\n0,6\n3,3\n2,6\n5,1\n1,2\n\
+5,5\n2,5\n6,5\n1,4\n0,4\n6,4\n1,1\n6,1\n1,0\n0,5\n1,6\n2,0\n";
+
+    fn sample() -> Memory {
+        Day18.parse(&Source::new("sample", SAMPLE_BYTES)).unwrap()
+    }
+
+    #[test]
+    fn parse_test() {
+        let memory = sample();
+        assert_eq!(memory.space, SAMPLE);
+        assert_eq!(memory.bytes[..2], [(5, 4), (4, 2)]);
+
+        let parse_error = |text| {
+            parse(&Source::new("d18.txt", text))
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            parse_error("5,4\n4;2\n"),
+            "d18.txt:2:4: expected x,y: '4;2'"
+        );
+        assert_eq!(
+            parse_error("5,4\n4,71\n"),
+            "d18.txt:2:3: expected a coordinate from 0 to 70: '71'"
+        );
+        assert_eq!(
+            parse_error("x,4\n"),
+            "d18.txt:1:1: expected a coordinate from 0 to 70: 'x'"
+        );
+
+        // a byte outside the sample's space means the real one
+        let memory = Day18
+            .parse(&Source::new("d18.txt", "5,4\n70,12\n"))
+            .unwrap();
+        assert_eq!(memory.space, REAL);
+    }
+
+    #[test]
+    fn shortest_path_test() {
+        assert_eq!(shortest_path(&corrupt(&[], 3)), Some(4));
+        assert_eq!(shortest_path(&corrupt(&[(1, 0), (1, 1)], 3)), Some(4));
+        assert_eq!(shortest_path(&corrupt(&[(0, 1), (1, 1), (2, 1)], 3)), None);
+        assert_eq!(shortest_path(&corrupt(&[(0, 0)], 3)), None);
+    }
+
+    #[test]
+    fn sample_test() {
+        assert_eq!(d18p1(&sample()), Ok(22));
+        assert_eq!(d18p2(&sample()), Ok((6, 1)));
+
+        // before any bytes fall, the way out is straight across and down
+        let memory = Memory {
+            space: Space { size: 8, fallen: 0 },
+            ..sample()
+        };
+        assert_eq!(d18p1(&memory), Ok(14));
+    }
+
+    #[test]
+    fn unsolved_test() {
+        // a wall across the top cuts off the exit straight away
+        let wall = Source::new("wall", "0,1\n1,1\n2,1\n3,1\n4,1\n5,1\n6,1\n");
+        assert_eq!(
+            Day18.solve_part(&wall, 1),
+            Ok(Answer::Unsolved(
+                "the exit is already cut off after 7 bytes".to_string()
+            ))
+        );
+        assert_eq!(Day18.solve_part(&wall, 2), Ok(Answer::from("6,1")));
+
+        let scattered = Source::new("scattered", "1,1\n3,3\n");
+        assert_eq!(
+            Day18.solve_part(&scattered, 2),
+            Ok(Answer::Unsolved("the exit is never cut off".to_string()))
+        );
+    }
+}
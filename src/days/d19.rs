@@ -1,18 +1,24 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
+use crate::utils::{self, ParseError, Source};
+use log::debug;
 use std::collections::HashMap;
 
-fn parse(file_path: &str) -> (Vec<String>, Vec<String>) {
-    let mut iter = utils::string_iter(file_path);
+fn parse(input: &Source) -> Result<(Vec<String>, Vec<String>), ParseError> {
+    let mut iter = input.lines();
     let patterns = iter
         .next()
-        .expect("Missing pattern header")
+        .ok_or_else(|| input.error("missing pattern header"))?
+        .text
         .split(", ")
         .map(str::to_string)
         .collect();
-    let empty_line_to_skip = iter.next().expect("Missing empty line");
-    assert!(empty_line_to_skip.is_empty());
-    let designs = iter.collect();
-    (patterns, designs)
+    match iter.next() {
+        Some(empty_line_to_skip) if empty_line_to_skip.text.is_empty() => {}
+        Some(line) => return Err(line.error("", "expected an empty line")),
+        None => return Err(input.error("missing empty line")),
+    }
+    let designs = iter.map(|line| line.text.to_string()).collect();
+    Ok((patterns, designs))
 }
 
 fn patterns_can_create<'a>(
@@ -20,7 +26,7 @@ fn patterns_can_create<'a>(
     pattern_slices: &Vec<&'a str>,
     memo: &mut HashMap<(&'a str, &'a str), usize>,
 ) -> bool {
-    pattern_combos(design, &pattern_slices, memo) > 0
+    pattern_combos(design, pattern_slices, memo) > 0
 }
 
 fn pattern_combos<'a>(
@@ -31,7 +37,7 @@ fn pattern_combos<'a>(
     patterns
         .iter()
         .map(|pattern| {
-            if utils::equals(design, *pattern) {
+            if utils::equals(design, pattern) {
                 1
             } else {
                 memo.get(&(design, *pattern)).copied().unwrap_or_else(|| {
@@ -50,8 +56,7 @@ fn pattern_combos<'a>(
         .sum()
 }
 
-pub fn d19p1(file_path: &str) -> usize {
-    let (patterns, designs) = parse(file_path);
+pub fn d19p1((patterns, designs): &(Vec<String>, Vec<String>)) -> usize {
     let pattern_slices: Vec<&str> = patterns.iter().map(String::as_str).collect();
 
     let mut combo_memoizer: HashMap<(&str, &str), usize> = HashMap::new();
@@ -60,14 +65,13 @@ pub fn d19p1(file_path: &str) -> usize {
         .iter()
         .enumerate()
         .filter(|(idx, design)| {
-            println!("Checking design {}: {}", idx, design);
+            debug!("Checking design {}: {}", idx, design);
             patterns_can_create(design, &pattern_slices, &mut combo_memoizer)
         })
         .count()
 }
 
-pub fn d19p2(file_path: &str) -> usize {
-    let (patterns, designs) = parse(file_path);
+pub fn d19p2((patterns, designs): &(Vec<String>, Vec<String>)) -> usize {
     let pattern_slices: Vec<&str> = patterns.iter().map(String::as_str).collect();
 
     let mut combo_memoizer: HashMap<(&str, &str), usize> = HashMap::new();
@@ -76,19 +80,28 @@ pub fn d19p2(file_path: &str) -> usize {
         .iter()
         .enumerate()
         .map(|(idx, design)| {
-            println!("Checking design {}: {}", idx, design);
+            debug!("Checking design {}: {}", idx, design);
             pattern_combos(design, &pattern_slices, &mut combo_memoizer)
         })
         .sum()
 }
 
-pub fn d19() {
-    //let file_path = "inputs/d19sample.txt";
-    let file_path = "inputs/d19.txt";
-    let mut result = d19p1(file_path);
-    println!("Result Day 19 Part 1: {}", result);
-    result = d19p2(file_path);
-    println!("Result Day 19 Part 2: {}", result);
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = (Vec<String>, Vec<String>);
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d19p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d19p2(input).into()
+    }
 }
 
 #[cfg(test)]
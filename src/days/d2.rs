@@ -1,11 +1,12 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
+use crate::utils::{self, ParseError, Source};
 
 // safe means monotonically increasing or decreasing and only differing by 1 to 3
 fn d2p1_is_safe(v: &Vec<u32>) -> bool {
     let (first, second) = (v[0] as i32, v[1] as i32);
     let direction = match second - first {
         0 => return false,
-        x if x > 3 || x < -3 => return false,
+        x if !(-3..=3).contains(&x) => return false,
         x if x > 0 => 1,
         _ => -1,
     };
@@ -30,9 +31,8 @@ fn d2p1_is_safe(v: &Vec<u32>) -> bool {
 }
 
 // count the number of "safe" rows
-pub fn d2p1(path: &str) -> i32 {
-    let input = utils::as_spaced_int_vec::<u32>(path);
-    input.filter(d2p1_is_safe).count() as i32
+pub fn d2p1(reports: &Vec<Vec<u32>>) -> i32 {
+    reports.iter().filter(|v| d2p1_is_safe(v)).count() as i32
 }
 
 // for part 2, more lenient version of safe:
@@ -44,16 +44,24 @@ fn d2p2_is_safe(v: &Vec<u32>) -> bool {
 
 // count the number of "safe" rows
 // if you allow removing one value to make the row safe
-pub fn d2p2(path: &str) -> i32 {
-    let input = utils::as_spaced_int_vec(path);
-    input.filter(d2p2_is_safe).count() as i32
+pub fn d2p2(reports: &Vec<Vec<u32>>) -> i32 {
+    reports.iter().filter(|v| d2p2_is_safe(v)).count() as i32
 }
 
-pub fn d2() {
-    //let path = "inputs/d2sample.txt";
-    let path = "inputs/d2.txt";
-    let mut result = d2p1(path);
-    println!("Result Day 2 Part 1: {}", result);
-    result = d2p2(path);
-    println!("Result Day 2 Part 2: {}", result);
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<Vec<u32>>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        utils::as_spaced_int_vec_from_source(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d2p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d2p2(input).into()
+    }
 }
@@ -1,15 +1,273 @@
-pub fn d20p1(_file_path: &str) -> usize {
-    0
+use crate::solution::{Answer, Solution};
+use crate::utils::matrix;
+use crate::utils::plane::{self, Direction};
+use crate::utils::{ParseError, Source};
+use log::debug;
+use std::collections::BTreeMap;
+
+const WALL: char = '#';
+const START: char = 'S';
+const END: char = 'E';
+
+// the fewest picoseconds a cheat has to save to be counted, in part 1 and part 2. The puzzle text
+// only gives answers for the sample for cheats that save at least 64 and at least 50
+const REAL_THRESHOLDS: (usize, usize) = (100, 100);
+const SAMPLE_THRESHOLDS: (usize, usize) = (64, 50);
+const SAMPLE_SIZE: (usize, usize) = (15, 15);
+
+pub struct Racetrack {
+    // every position on the track, from the start to the end
+    path: Vec<(usize, usize)>,
+    // how many picoseconds it takes to reach each position, None for walls
+    time: Vec<Vec<Option<usize>>>,
+    thresholds: (usize, usize),
 }
 
-pub fn d20p2(_file_path: &str) -> usize {
-    0
+// the sample is a much smaller racetrack than the real input
+fn thresholds(grid: &Vec<Vec<char>>) -> (usize, usize) {
+    if matrix::dimensions(grid) == SAMPLE_SIZE {
+        SAMPLE_THRESHOLDS
+    } else {
+        REAL_THRESHOLDS
+    }
 }
 
-pub fn d20() {
-    let file_path = "inputs/d20sample.txt";
-    let mut result = d20p1(file_path);
-    println!("Result Day 20 Part 1: {}", result);
-    result = d20p2(file_path);
-    println!("Result Day 20 Part 2: {}", result);
+// there is only one track, so follow it from the start, never turning back
+fn follow_track(input: &Source, grid: &Vec<Vec<char>>) -> Result<Racetrack, ParseError> {
+    let (row_count, col_count) = matrix::dimensions(grid);
+    let mut time = vec![vec![None; col_count]; row_count];
+    let mut path = vec![plane::find_unique_element(grid, START)];
+    let (mut row, mut col) = path[0];
+    time[row][col] = Some(0);
+
+    while grid[row][col] != END {
+        let next = Direction::all()
+            .into_iter()
+            .map(|dir| plane::move_one(row, col, row_count, col_count, dir))
+            .find(|next| {
+                !next.out_of_bounds
+                    && grid[next.row][next.col] != WALL
+                    && time[next.row][next.col].is_none()
+            });
+        let Some(next) = next else {
+            let line = input.lines().nth(row).expect("the grid has a row per line");
+            let (offset, c) = line
+                .text
+                .char_indices()
+                .nth(col)
+                .expect("and a column per char");
+            return Err(line.error(
+                &line.text[offset..offset + c.len_utf8()],
+                format!("the track ends here before reaching the end '{}'", END),
+            ));
+        };
+        (row, col) = (next.row, next.col);
+        time[row][col] = Some(path.len());
+        path.push((row, col));
+    }
+
+    Ok(Racetrack {
+        path,
+        time,
+        thresholds: thresholds(grid),
+    })
+}
+
+// how many cheats save each amount of time, for cheats of up to max_cheat picoseconds. A cheat is
+// identified by where it starts and ends, so it is enough to look at every pair of track positions
+// no more than max_cheat apart
+pub fn savings(track: &Racetrack, max_cheat: usize) -> BTreeMap<usize, usize> {
+    let (row_count, col_count) = matrix::dimensions(&track.time);
+    let max_cheat = max_cheat as isize;
+    let mut counts = BTreeMap::new();
+
+    for (from, &(row, col)) in track.path.iter().enumerate() {
+        for d_row in -max_cheat..=max_cheat {
+            let reach = max_cheat - d_row.abs();
+            for d_col in -reach..=reach {
+                let (to_row, to_col) = (row as isize + d_row, col as isize + d_col);
+                if !matrix::in_bounds(row_count, col_count, to_row, to_col) {
+                    continue;
+                }
+                let Some(to) = track.time[to_row as usize][to_col as usize] else {
+                    continue;
+                };
+                let cheat_time = (d_row.abs() + d_col.abs()) as usize;
+                if to > from + cheat_time {
+                    *counts.entry(to - from - cheat_time).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+// the savings as the puzzle text lists them
+pub fn describe_savings(counts: &BTreeMap<usize, usize>) -> Vec<String> {
+    counts
+        .iter()
+        .map(|(saved, count)| match count {
+            1 => format!("There is one cheat that saves {} picoseconds.", saved),
+            _ => format!(
+                "There are {} cheats that save {} picoseconds.",
+                count, saved
+            ),
+        })
+        .collect()
+}
+
+pub fn count_cheats(track: &Racetrack, max_cheat: usize, threshold: usize) -> usize {
+    let counts = savings(track, max_cheat);
+    for line in describe_savings(&counts) {
+        debug!("{}", line);
+    }
+    counts.range(threshold..).map(|(_, count)| count).sum()
+}
+
+pub fn d20p1(track: &Racetrack) -> usize {
+    count_cheats(track, 2, track.thresholds.0)
+}
+
+pub fn d20p2(track: &Racetrack) -> usize {
+    count_cheats(track, 20, track.thresholds.1)
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Racetrack;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        let grid = matrix::as_char_matrix_from_str(&input.text);
+        plane::check_unique_element(input, &grid, START, "start")?;
+        plane::check_unique_element(input, &grid, END, "end")?;
+        follow_track(input, &grid)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d20p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d20p2(input).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############
+";
+
+    fn sample() -> Racetrack {
+        Day20.parse(&Source::new("sample", SAMPLE)).unwrap()
+    }
+
+    #[test]
+    fn follow_track_test() {
+        let track = sample();
+        assert_eq!(track.path.len(), 85);
+        assert_eq!(track.path[0], (3, 1));
+        assert_eq!(track.path[84], (7, 5));
+        assert_eq!(track.time[7][5], Some(84));
+        assert_eq!(track.time[0][0], None);
+        assert_eq!(track.thresholds, SAMPLE_THRESHOLDS);
+
+        let straight = Day20.parse(&Source::new("straight", "#####\n#S.E#\n#####\n"));
+        assert_eq!(straight.unwrap().thresholds, REAL_THRESHOLDS);
+    }
+
+    #[test]
+    fn savings_test() {
+        let counts = savings(&sample(), 2);
+        assert_eq!(
+            counts.into_iter().collect::<Vec<_>>(),
+            vec![
+                (2, 14),
+                (4, 14),
+                (6, 2),
+                (8, 4),
+                (10, 2),
+                (12, 3),
+                (20, 1),
+                (36, 1),
+                (38, 1),
+                (40, 1),
+                (64, 1)
+            ]
+        );
+
+        let counts = savings(&sample(), 20);
+        assert_eq!(
+            counts.range(50..).collect::<Vec<_>>(),
+            vec![
+                (&50, &32),
+                (&52, &31),
+                (&54, &29),
+                (&56, &39),
+                (&58, &25),
+                (&60, &23),
+                (&62, &20),
+                (&64, &19),
+                (&66, &12),
+                (&68, &14),
+                (&70, &12),
+                (&72, &22),
+                (&74, &4),
+                (&76, &3)
+            ]
+        );
+    }
+
+    #[test]
+    fn describe_savings_test() {
+        let counts = BTreeMap::from([(2, 14), (64, 1)]);
+        assert_eq!(
+            describe_savings(&counts),
+            vec![
+                "There are 14 cheats that save 2 picoseconds.",
+                "There is one cheat that saves 64 picoseconds."
+            ]
+        );
+    }
+
+    #[test]
+    fn sample_test() {
+        // one part 1 cheat saves at least 64, and 285 part 2 cheats save at least 50
+        assert_eq!(d20p1(&sample()), 1);
+        assert_eq!(d20p2(&sample()), 285);
+        assert_eq!(count_cheats(&sample(), 2, 20), 5);
+    }
+
+    #[test]
+    fn parse_errors_test() {
+        let parse_error = |text| match Day20.parse(&Source::new("d20.txt", text)) {
+            Ok(_) => panic!("{:?} parsed", text),
+            Err(err) => err.to_string(),
+        };
+        assert_eq!(
+            parse_error("#S.#\n"),
+            "d20.txt: expected exactly one end 'E'"
+        );
+        assert_eq!(
+            parse_error("#####\n#S.#E\n#####\n"),
+            "d20.txt:2:3: the track ends here before reaching the end 'E': '.'"
+        );
+    }
 }
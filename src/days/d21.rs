@@ -1,4 +1,6 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
+use crate::utils::{ParseError, Source};
+use log::debug;
 use std::collections::HashMap;
 
 #[derive(PartialEq, Eq, Debug, PartialOrd, Ord, Clone, Hash, Copy)]
@@ -122,7 +124,7 @@ fn l1_shortest_between_2(
 
     let mut my_seen = seen.clone();
     let result = match adj.get(&(src, dest)) {
-        Some(dir) => vec![vec![dir.clone()]],
+        Some(dir) => vec![vec![*dir]],
         _ => {
             my_seen.push(src);
 
@@ -137,7 +139,7 @@ fn l1_shortest_between_2(
                         match adj.get(&(src, *next_dest)) {
                             Some(dir) => {
                                 my_seen.push(*next_dest);
-                                vec![(dir.clone(), *next_dest)]
+                                vec![(*dir, *next_dest)]
                             }
                             _ => vec![],
                         }
@@ -154,7 +156,7 @@ fn l1_shortest_between_2(
             for (next_direction, next_dest) in next_step_options {
                 let next_paths = l1_shortest_between_2(adj, &my_seen, next_dest, dest, memoizer);
                 for mut next_path in next_paths {
-                    next_path.insert(0, next_direction.clone());
+                    next_path.insert(0, next_direction);
                     paths_from_here.push(next_path.clone());
                 }
             }
@@ -187,7 +189,7 @@ fn l1_shortest_paths(
             steps_option_to_button.push(DirectionAndPush::Push);
             current_button_options.push(steps_option_to_button);
         }
-        current_pos = l1_button.clone();
+        current_pos = l1_button;
         options_per_step.push(current_button_options.clone());
         current_button_options.clear();
     }
@@ -269,14 +271,14 @@ fn l2_shortest_between_2(
 
     // .0 is the next step, .1 is the next step's destination
     let result = if next_step_options.len() == 1 && next_step_options[0].1 == dest {
-        return vec![vec![next_step_options[0].0.clone()]];
+        return vec![vec![next_step_options[0].0]];
     } else {
         let mut paths_from_here = Vec::new();
 
         for (next_direction, next_dest) in next_step_options {
             let next_paths = l2_shortest_between_2(next_dest, dest, memoizer);
             for mut next_path in next_paths {
-                next_path.insert(0, next_direction.clone());
+                next_path.insert(0, next_direction);
                 paths_from_here.push(next_path.clone());
             }
         }
@@ -310,13 +312,98 @@ fn l2_shortest_paths(
     combine_button_options(options_per_step)
 }
 
+// part 1 has 2 robots on directional keypads between you and the one at the numeric keypad, and
+// part 2 has 25. get_complexity works out the same as part 1 by trying every path
+const P1_DEPTH: usize = 2;
+const P2_DEPTH: usize = 25;
+
+// (from, to, depth) -> fewest presses
+type CostMemoizer = HashMap<(DirectionAndPush, DirectionAndPush, usize), usize>;
+
+// the fewest presses you need to make so that the directional keypad `depth` keypads away from yours
+// moves from src to dest and pushes it. Depth 0 is your own keypad, where that is a single press.
+// Every move on a keypad starts and ends with the keypad before it on A, so the cost of a move only
+// depends on the move and the depth, never on what came before it
+fn press_cost(
+    src: DirectionAndPush,
+    dest: DirectionAndPush,
+    depth: usize,
+    memoizer: &mut CostMemoizer,
+) -> usize {
+    if depth == 0 {
+        return 1;
+    }
+    if let Some(memoized) = memoizer.get(&(src, dest, depth)) {
+        return *memoized;
+    }
+
+    let cost = l2_shortest_between_2(src, dest, &mut HashMap::new())
+        .into_iter()
+        .map(|mut path| {
+            path.push(DirectionAndPush::Push);
+            sequence_cost(&path, depth - 1, memoizer)
+        })
+        .min()
+        .unwrap();
+
+    memoizer.insert((src, dest, depth), cost);
+    cost
+}
+
+// the fewest presses you need to make so that the keypad `depth` keypads away presses each of buttons
+fn sequence_cost(
+    buttons: &Vec<DirectionAndPush>,
+    depth: usize,
+    memoizer: &mut CostMemoizer,
+) -> usize {
+    let mut current_pos = START_DIR;
+    let mut cost = 0;
+    for button in buttons {
+        cost += press_cost(current_pos, *button, depth, memoizer);
+        current_pos = *button;
+    }
+    cost
+}
+
+// the fewest presses you need to make to type code on the numeric keypad, with `depth` robot operated
+// directional keypads in between. Only the cost is kept, rather than every path, so any depth is quick
+pub fn code_cost(code: &str, depth: usize, memoizer: &mut CostMemoizer) -> usize {
+    let adj = get_l1_adjacency_map();
+    let l1_memoizer = &mut HashMap::new();
+    let mut current_pos = START_CHAR;
+    let mut cost = 0;
+    for l1_button in code.chars() {
+        cost += l1_shortest_between_2(&adj, &vec![], current_pos, l1_button, l1_memoizer)
+            .into_iter()
+            .map(|mut path| {
+                path.push(DirectionAndPush::Push);
+                sequence_cost(&path, depth, memoizer)
+            })
+            .min()
+            .unwrap();
+        current_pos = l1_button;
+    }
+    cost
+}
+
+// codes are checked by parse to be digits followed by A
+fn numeric_part(code: &str) -> usize {
+    code[0..code.len() - 1].parse::<usize>().unwrap()
+}
+
+pub fn complexity_at_depth(code: &str, depth: usize, memoizer: &mut CostMemoizer) -> usize {
+    let presses = code_cost(code, depth, memoizer);
+    debug!("{} at depth {}: {} presses", code, depth, presses);
+    numeric_part(code) * presses
+}
+
 pub fn get_complexity(s: String) -> usize {
-    println!("s: {}", s);
+    debug!("s: {}", s);
     let l1_desired_buttons: Vec<char> = s.chars().collect();
     let l1_paths = l1_shortest_paths(&get_l1_adjacency_map(), l1_desired_buttons);
     let l1_min = l1_paths.iter().map(|p| p.len()).min().unwrap();
     let l1_max = l1_paths.iter().map(|p| p.len()).max().unwrap();
-    println!(
+    debug!(
         "L1 path count {} / min {} / max {}",
         l1_paths.len(),
         l1_min,
@@ -334,7 +421,7 @@ pub fn get_complexity(s: String) -> usize {
         .collect();
     let l2_min = l2_paths.iter().map(|p| p.len()).min().unwrap();
     let l2_max = l2_paths.iter().map(|p| p.len()).max().unwrap();
-    println!(
+    debug!(
         "L2 path count {} / min {} / max {}",
         l2_paths.len(),
         l2_min,
@@ -349,44 +436,84 @@ pub fn get_complexity(s: String) -> usize {
         .collect();
     let l3_min = l3_paths.iter().map(|p| p.len()).min().unwrap();
     let l3_max = l3_paths.iter().map(|p| p.len()).max().unwrap();
-    println!(
+    debug!(
         "L3 path count {} / min {} / max {}",
         l3_paths.len(),
         l3_min,
         l3_max
     );
 
-    // all inputs are integer plus A
-    let parsed_from_input = s[0..s.len() - 1].parse::<usize>().unwrap();
+    let parsed_from_input = numeric_part(&s);
     let complexity = parsed_from_input * l3_min;
-    println!(
+    debug!(
         "min L3 * parsed: {} * {} = {}",
         l3_min, parsed_from_input, complexity
     );
     complexity
 }
 
-pub fn d21p1(file_path: &str) -> usize {
-    utils::string_iter(file_path).map(get_complexity).sum()
+// every code is a number typed on the numeric keypad followed by A, e.g. 029A
+fn parse(input: &Source) -> Result<Vec<String>, ParseError> {
+    input
+        .lines()
+        .map(|line| match line.text.strip_suffix(START_CHAR) {
+            Some(digits) if !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()) => {
+                Ok(line.text.to_string())
+            }
+            _ => Err(line.error("", "expected digits followed by A")),
+        })
+        .collect()
 }
 
-pub fn d21p2(_file_path: &str) -> usize {
-    0
+pub fn d21p1(codes: &Vec<String>) -> usize {
+    let memoizer = &mut HashMap::new();
+    codes
+        .iter()
+        .map(|code| complexity_at_depth(code, P1_DEPTH, memoizer))
+        .sum()
 }
 
-pub fn d21() {
-    //let file_path = "inputs/d21sample.txt";
-    let file_path = "inputs/d21.txt";
-    let mut result = d21p1(file_path);
-    println!("Result Day 21 Part 1: {}", result);
-    result = d21p2(file_path);
-    println!("Result Day 21 Part 2: {}", result);
+pub fn d21p2(codes: &Vec<String>) -> usize {
+    let memoizer = &mut HashMap::new();
+    codes
+        .iter()
+        .map(|code| complexity_at_depth(code, P2_DEPTH, memoizer))
+        .sum()
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d21p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d21p2(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse(&Source::new("ok", "029A\n980A\n")),
+            Ok(vec!["029A".to_string(), "980A".to_string()])
+        );
+        for bad in ["029\n", "A\n", "0x9A\n", "\n"] {
+            assert!(parse(&Source::new("bad", bad)).is_err(), "{:?}", bad);
+        }
+    }
+
     #[test]
     fn test_l1_shortest_between_2() {
         let adjacency = get_l1_adjacency_map();
@@ -646,6 +773,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn press_cost_test() {
+        let memoizer = &mut HashMap::new();
+        assert_eq!(
+            press_cost(DirectionAndPush::Push, DirectionAndPush::Left, 0, memoizer),
+            1
+        );
+        // v<<A on the keypad in front of yours
+        assert_eq!(
+            press_cost(DirectionAndPush::Push, DirectionAndPush::Left, 1, memoizer),
+            4
+        );
+        assert_eq!(
+            sequence_cost(
+                &vec![DirectionAndPush::Left, DirectionAndPush::Push],
+                1,
+                memoizer
+            ),
+            8
+        );
+    }
+
+    #[test]
+    fn code_cost_test() {
+        let memoizer = &mut HashMap::new();
+        // <A^A>^^AvvvA, then v<<A>>^A<A>AvA<^AA>A<vAAA>^A from the puzzle text
+        assert_eq!(code_cost("029A", 0, memoizer), 12);
+        assert_eq!(code_cost("029A", 1, memoizer), 28);
+        assert_eq!(code_cost("029A", P1_DEPTH, memoizer), 68);
+    }
+
+    #[test]
+    fn depth_matches_part1_test() {
+        let codes = vec!["029A", "980A", "179A", "456A", "379A"];
+        let memoizer = &mut HashMap::new();
+        for code in &codes {
+            assert_eq!(
+                complexity_at_depth(code, P1_DEPTH, memoizer),
+                get_complexity(code.to_string())
+            );
+        }
+        let codes: Vec<String> = codes.into_iter().map(str::to_string).collect();
+        assert_eq!(d21p1(&codes), 126384);
+        assert_eq!(d21p2(&codes), 154115708116294);
+    }
+
     #[test]
     fn combine_button_options_test() {
         let mut v = vec![vec![vec![1]]];
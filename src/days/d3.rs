@@ -1,14 +1,16 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
+use crate::utils::{self, ParseError, Source};
+use log::trace;
 use regex::Regex;
 
 fn memory_result(v: Vec<String>) -> i32 {
     let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
 
     v.iter()
-        .flat_map(|line| re.captures_iter(&line.as_str()))
+        .flat_map(|line| re.captures_iter(line.as_str()))
         .map(|c| {
             let (_, [a_str, b_str]) = c.extract();
-            println!("match: {:?},{:?}", a_str, b_str);
+            trace!("match: {:?},{:?}", a_str, b_str);
             let a: i32 = a_str.parse().expect("integer expected");
             let b: i32 = b_str.parse().expect("integer expected");
             a * b
@@ -16,21 +18,18 @@ fn memory_result(v: Vec<String>) -> i32 {
         .sum()
 }
 
-fn d3p1(path: &str) -> i32 {
-    // I'd prefer to keep this as an iterator, but this is necessary to ensure that ownership of the Strings
-    // remains outside of the iterator map step, where it would go out of scope before use
-    let owned_lines: Vec<String> = utils::string_iter(path).collect();
-    memory_result(owned_lines)
+pub fn d3p1(lines: &Vec<String>) -> i32 {
+    memory_result(lines.clone())
 }
 
-fn d3p2(path: &str) -> i32 {
+pub fn d3p2(lines: &Vec<String>) -> i32 {
     let enabler = "do()";
     let disabler = "don't()";
 
     let mut enabled = true;
     let mut enabled_lines: Vec<String> = Vec::new();
 
-    utils::string_iter(path).for_each(|line| {
+    lines.iter().for_each(|line| {
         let mut remaining = line.as_str();
         while !remaining.is_empty() {
             if enabled {
@@ -42,13 +41,14 @@ fn d3p2(path: &str) -> i32 {
                         remaining = &remaining[idx + disabler.len()..];
                         enabled = false;
 
-                        println!(
+                        trace!(
                             "disabler found.  enabled: {}, remaining: {}",
-                            enabled_line, remaining
+                            enabled_line,
+                            remaining
                         );
                     }
                     None => {
-                        println!("disabler not found.  remaining: {}", remaining);
+                        trace!("disabler not found.  remaining: {}", remaining);
 
                         enabled_lines.push(remaining.to_string());
                         remaining = "";
@@ -62,13 +62,14 @@ fn d3p2(path: &str) -> i32 {
                         remaining = &remaining[idx + enabler.len()..];
                         enabled = true;
 
-                        println!(
+                        trace!(
                             "enabler found.  disabled: {}, remaining: {}",
-                            disabled_line, remaining
+                            disabled_line,
+                            remaining
                         );
                     }
                     None => {
-                        println!("enabler not found.  remaining: {}", remaining);
+                        trace!("enabler not found.  remaining: {}", remaining);
 
                         remaining = "";
                     }
@@ -80,12 +81,22 @@ fn d3p2(path: &str) -> i32 {
     memory_result(enabled_lines)
 }
 
-pub fn d3() {
-    //let path = "inputs/d3sample.txt";
-    //let path = "inputs/d3sample2.txt";
-    let path = "inputs/d3.txt";
-    let mut result = d3p1(path);
-    println!("Result Day 3 Part 1: {}", result);
-    result = d3p2(path);
-    println!("Result Day 3 Part 2: {}", result);
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        // I'd prefer to keep this as an iterator, but this is necessary to ensure that ownership of the Strings
+        // remains outside of the iterator map step, where it would go out of scope before use
+        Ok(utils::string_iter_from_str(&input.text).collect())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d3p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d3p2(input).into()
+    }
 }
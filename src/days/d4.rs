@@ -1,4 +1,5 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
+use crate::utils::{self, ParseError, Source};
 
 fn vec_scan(v: &Vec<char>, to_scan: &str) -> i32 {
     if v.len() < to_scan.len() {
@@ -64,10 +65,9 @@ fn diagonal_matrix_scan(mat: &Vec<Vec<char>>, to_scan: &str) -> i32 {
     count
 }
 
-fn d4p1(path: &str) -> i32 {
+pub fn d4p1(mat: &Vec<Vec<char>>) -> i32 {
     let scan_pattern = "XMAS";
 
-    let mut mat = utils::matrix::as_char_matrix(path);
     let mut count: i32 = 0;
 
     count += mat
@@ -76,7 +76,7 @@ fn d4p1(path: &str) -> i32 {
         .sum::<i32>();
 
     // ok to replace original because it does not change the diagonal search
-    mat = utils::matrix::flip_matrix(&mat);
+    let mat = utils::matrix::flip_matrix(mat);
 
     count += mat
         .iter()
@@ -90,15 +90,15 @@ fn check_xmas(mat: &Vec<Vec<char>>) -> bool {
     const MAS_LEN: usize = 3;
 
     if mat.len() < MAS_LEN || mat[0].len() < MAS_LEN || mat[1][1] != 'A' {
-        return false;
+        false
     } else {
-        match (mat[0][0], mat[0][2], mat[2][0], mat[2][2]) {
-            ('M', 'M', 'S', 'S') => true,
-            ('M', 'S', 'M', 'S') => true,
-            ('S', 'M', 'S', 'M') => true,
-            ('S', 'S', 'M', 'M') => true,
-            _ => false,
-        }
+        matches!(
+            (mat[0][0], mat[0][2], mat[2][0], mat[2][2]),
+            ('M', 'M', 'S', 'S')
+                | ('M', 'S', 'M', 'S')
+                | ('S', 'M', 'S', 'M')
+                | ('S', 'S', 'M', 'M')
+        )
     }
 }
 
@@ -138,19 +138,26 @@ fn count_xmases(mat: &Vec<Vec<char>>) -> i32 {
     count
 }
 
-fn d4p2(path: &str) -> i32 {
-    let mat = utils::matrix::as_char_matrix(path);
-    count_xmases(&mat)
+pub fn d4p2(mat: &Vec<Vec<char>>) -> i32 {
+    count_xmases(mat)
 }
 
-pub fn d4() {
-    //let path = "inputs/d4sample1.txt";
-    //let path = "inputs/d4sample2.txt";
-    let path = "inputs/d4.txt";
-    let mut result = d4p1(path);
-    println!("Result Day 4 Part 1: {}", result);
-    result = d4p2(path);
-    println!("Result Day 4 Part 2: {}", result);
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        Ok(utils::matrix::as_char_matrix_from_str(&input.text))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d4p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d4p2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -230,9 +237,9 @@ mod tests {
 
     #[test]
     fn check_xmas_too_small() {
-        assert_eq!(check_xmas(&vec![vec![]]), false);
-        assert_eq!(check_xmas(&vec![vec!['A']]), false);
-        assert_eq!(check_xmas(&vec![vec!['A', 'A'], vec!['A', 'A']]), false);
+        assert!(!check_xmas(&vec![vec![]]));
+        assert!(!check_xmas(&vec![vec!['A']]));
+        assert!(!check_xmas(&vec![vec!['A', 'A'], vec!['A', 'A']]));
     }
 
     #[test]
@@ -242,7 +249,7 @@ mod tests {
             vec!['A', 'A', 'A'],
             vec!['A', 'A', 'A'],
         ];
-        assert_eq!(check_xmas(&matrix), false);
+        assert!(!check_xmas(&matrix));
     }
 
     #[test]
@@ -1,46 +1,66 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
+use crate::utils::{Line, ParseError, Source};
 use multimap::MultiMap;
 use std::cmp::Ordering::{self, Greater, Less};
 
-fn parse_rule(s: String) -> (u32, u32) {
-    let mut parts = s.split('|');
-    let a = parts
-        .next()
-        .unwrap()
-        .parse()
-        .expect(format!("could not parse integer a from {}", s).as_str());
-    let b = parts
-        .next()
-        .unwrap()
-        .parse()
-        .expect(format!("could not parse integer b from {}", s).as_str());
-    if parts.next().is_some() {
-        panic!("expected rule in format a|b, saw: {}", s);
-    }
-    (a, b)
+fn parse_rule(line: &Line) -> Result<(u32, u32), ParseError> {
+    let parts: Vec<&str> = line.text.split('|').collect();
+    match parts[..] {
+        [a, b] => Ok((
+            line.parse(a, "a page number")?,
+            line.parse(b, "a page number")?,
+        )),
+        [_, _, extra, ..] => Err(line.error(extra, "expected rule in format a|b")),
+        _ => Err(line.error("", "expected rule in format a|b")),
+    }
 }
 
-fn parse_page_data(s: String) -> Vec<u32> {
-    s.split(',')
-        .map(|x| {
-            x.parse()
-                .expect(format!("could not parse page_data from {}", s).as_str())
-        })
+fn parse_page_data(line: &Line) -> Result<Vec<u32>, ParseError> {
+    line.text
+        .split(',')
+        .map(|x| line.parse(x, "a page number"))
         .collect()
 }
 
-fn parse_pages(i: impl Iterator<Item = String>) -> (MultiMap<u32, u32>, Vec<Vec<u32>>) {
+// an update needs a middle page, and a rule for every pair of its pages so that it can be sorted
+fn check_page_data(
+    line: &Line,
+    data: &Vec<u32>,
+    rules: &MultiMap<u32, u32>,
+) -> Result<(), ParseError> {
+    if data.len().is_multiple_of(2) {
+        return Err(line.error("", "expected an odd number of pages"));
+    }
+    for (i, a) in data.iter().enumerate() {
+        for b in &data[i + 1..] {
+            let ordered = |x, y| {
+                rules
+                    .get_vec(x)
+                    .is_some_and(|after: &Vec<u32>| after.contains(y))
+            };
+            if !ordered(a, b) && !ordered(b, a) {
+                return Err(line.error("", format!("no rule orders pages {} and {}", a, b)));
+            }
+        }
+    }
+    Ok(())
+}
+
+// the ordering rules, then the updates to check against them
+type Manual = (MultiMap<u32, u32>, Vec<Vec<u32>>);
+
+fn parse_pages(input: &Source) -> Result<Manual, ParseError> {
     let mut rules = MultiMap::new();
     let mut page_data = Vec::new();
 
     // parse file in 2 phases: rules, then data
     let mut parsing_rules = true;
 
-    for line in i {
-        match (parsing_rules, line.len()) {
+    for line in input.lines() {
+        match (parsing_rules, line.text.len()) {
             // first, parse rules
             (true, n) if n > 0 => {
-                let (a, b) = parse_rule(line);
+                let (a, b) = parse_rule(&line)?;
                 rules.insert(a, b);
             }
             // then switch to parsing page data
@@ -49,26 +69,21 @@ fn parse_pages(i: impl Iterator<Item = String>) -> (MultiMap<u32, u32>, Vec<Vec<
             }
             // parse page data
             (false, n) if n > 0 => {
-                page_data.push(parse_page_data(line));
+                let data = parse_page_data(&line)?;
+                check_page_data(&line, &data, &rules)?;
+                page_data.push(data);
             }
             // end of file
-            _ => return (rules, page_data),
+            _ => return Ok((rules, page_data)),
         }
     }
 
-    (rules, page_data)
+    Ok((rules, page_data))
 }
 
+// parsing checks every update has an odd number of pages
 fn middle_value(data: &Vec<u32>) -> u32 {
-    if data.len() % 2 == 0 {
-        panic!(
-            "expected odd number of pages, got {} for {:?}",
-            data.len(),
-            data
-        );
-    } else {
-        data[data.len() / 2]
-    }
+    data[data.len() / 2]
 }
 
 // a rule means: KEY must always come before any of the VALUEs in the data
@@ -77,8 +92,8 @@ fn pages_in_order(data: &Vec<u32>, rules: &MultiMap<u32, u32>) -> bool {
     let mut before_me: Vec<u32> = Vec::new();
     for page in data {
         for must_come_before in &before_me {
-            if let Some(to_check) = rules.get_vec(&page) {
-                if to_check.contains(&must_come_before) {
+            if let Some(to_check) = rules.get_vec(page) {
+                if to_check.contains(must_come_before) {
                     return false;
                 }
             }
@@ -110,8 +125,8 @@ fn rules_comparator(a: &u32, b: &u32, rules: &MultiMap<u32, u32>) -> Ordering {
         }
     }
 
-    // if this isn't a valid assumption, I'll know quickly
-    panic!("{} and {} do not have a well defined ordering", a, b);
+    // parsing checks every pair of pages in an update has a rule, so this is the same page
+    Ordering::Equal
 }
 
 fn corrected_order(data: &Vec<u32>, rules: &MultiMap<u32, u32>) -> Vec<u32> {
@@ -131,29 +146,30 @@ fn p2_result(data: &Vec<u32>, rules: &MultiMap<u32, u32>) -> u32 {
     }
 }
 
-fn d5p1(path: &str) -> u32 {
-    let (rules, page_data) = parse_pages(utils::string_iter(path));
-    page_data
-        .into_iter()
-        .map(|data| p1_result(&data, &rules))
-        .sum()
+pub fn d5p1((rules, page_data): &(MultiMap<u32, u32>, Vec<Vec<u32>>)) -> u32 {
+    page_data.iter().map(|data| p1_result(data, rules)).sum()
 }
 
-fn d5p2(path: &str) -> u32 {
-    let (rules, page_data) = parse_pages(utils::string_iter(path));
-    page_data
-        .into_iter()
-        .map(|data| p2_result(&data, &rules))
-        .sum()
+pub fn d5p2((rules, page_data): &(MultiMap<u32, u32>, Vec<Vec<u32>>)) -> u32 {
+    page_data.iter().map(|data| p2_result(data, rules)).sum()
 }
 
-pub fn d5() {
-    //let path = "inputs/d5sample.txt";
-    let path = "inputs/d5.txt";
-    let mut result = d5p1(path);
-    println!("Result Day 5 Part 1: {}", result);
-    result = d5p2(path);
-    println!("Result Day 5 Part 2: {}", result);
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = (MultiMap<u32, u32>, Vec<Vec<u32>>);
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        parse_pages(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d5p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d5p2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -162,32 +178,46 @@ mod tests {
 
     #[test]
     fn parse_rule_simple() {
-        let (a, b) = parse_rule("1|2000".to_string());
+        let (a, b) = parse_rule(&Line::new("test", 1, "1|2000")).unwrap();
         assert_eq!(a, 1);
         assert_eq!(b, 2000);
     }
 
+    #[test]
+    fn parse_rule_errors() {
+        let err = parse_rule(&Line::new("d5.txt", 4, "12|x3")).unwrap_err();
+        assert_eq!(err.to_string(), "d5.txt:4:4: expected a page number: 'x3'");
+        let err = parse_rule(&Line::new("d5.txt", 4, "12|13|14")).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (7, "14"));
+        assert!(parse_rule(&Line::new("d5.txt", 4, "12")).is_err());
+    }
+
     #[test]
     fn parse_page_data_simple() {
         assert_eq!(
-            parse_page_data("1,2,3,4,50000".to_string()),
-            vec![1, 2, 3, 4, 50000]
+            parse_page_data(&Line::new("test", 1, "1,2,3,4,50000")),
+            Ok(vec![1, 2, 3, 4, 50000])
         );
     }
 
     #[test]
     fn parse_pages_simple() {
-        let source_data = vec![
-            "1|2".to_string(),
-            "3|4".to_string(),
-            "".to_string(),
-            "1,2,3,4,50000".to_string(),
-            "9,10,11".to_string(),
-        ];
-        let (rules, page_data) = parse_pages(source_data.into_iter());
-        assert_eq!(*rules.get_vec(&1).unwrap(), vec![2u32]);
+        let source_data = Source::new("test", "1|2\n3|4\n2|3\n1|3\n\n1,2,3\n3,1,2\n50000\n");
+        let (rules, page_data) = parse_pages(&source_data).unwrap();
+        assert_eq!(*rules.get_vec(&1).unwrap(), vec![2u32, 3]);
         assert_eq!(*rules.get_vec(&3).unwrap(), vec![4u32]);
-        assert_eq!(page_data, vec![vec![1, 2, 3, 4, 50000], vec![9, 10, 11]]);
+        assert_eq!(page_data, vec![vec![1, 2, 3], vec![3, 1, 2], vec![50000]]);
+
+        let bad_data = Source::new("d5.txt", "1|2\n\n1,two,3\n");
+        let err = parse_pages(&bad_data).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+
+        let even = Source::new("d5.txt", "1|2\n\n1,2\n");
+        let err = parse_pages(&even).unwrap_err();
+        assert_eq!(err.message, "expected an odd number of pages");
+        let unordered = Source::new("d5.txt", "1|2\n2|3\n\n1,2,3\n");
+        let err = parse_pages(&unordered).unwrap_err();
+        assert_eq!(err.message, "no rule orders pages 1 and 3");
     }
 
     #[test]
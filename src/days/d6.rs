@@ -1,5 +1,8 @@
+use crate::solution::{Answer, Solution};
 use crate::utils::matrix;
 use crate::utils::plane;
+use crate::utils::{ParseError, Source};
+use log::{debug, trace};
 
 const GUARD: char = '^';
 const OBSTACLE: char = '#';
@@ -16,10 +19,9 @@ fn find_guard(plane: &Vec<Vec<char>>) -> plane::MovingObject {
     }
 }
 
-fn d6p1(file_path: &str) -> usize {
-    let plane = matrix::as_char_matrix(file_path);
-    let guard = find_guard(&plane);
-    if let Some(path_to_exit) = plane::path_to_exit_turning_right(&plane, &vec![OBSTACLE], &guard) {
+pub fn d6p1(plane: &Vec<Vec<char>>) -> usize {
+    let guard = find_guard(plane);
+    if let Some(path_to_exit) = plane::path_to_exit_turning_right(plane, &vec![OBSTACLE], &guard) {
         let unique_spaces = plane::unique_spaces(&path_to_exit);
         unique_spaces.len()
     } else {
@@ -27,29 +29,26 @@ fn d6p1(file_path: &str) -> usize {
     }
 }
 
-fn d6p2(file_path: &str) -> usize {
+pub fn d6p2(plane: &Vec<Vec<char>>) -> usize {
     const NEW_OBSTACLE: char = 'O';
 
-    let plane = matrix::as_char_matrix(file_path);
-    let guard = find_guard(&plane);
-    let alt_planes = matrix::replace_one_cell(&plane, NEW_OBSTACLE);
-    println!("Alt planes: {:?}", alt_planes.len());
+    let guard = find_guard(plane);
+    let alt_planes = matrix::replace_one_cell(plane, NEW_OBSTACLE);
+    debug!("Alt planes: {:?}", alt_planes.len());
     alt_planes
         .iter()
         .enumerate()
         .map(|(idx, alt_plane)| {
             if idx % 100 == 0 {
-                println!("Alt plane: {}", idx);
+                trace!("Alt plane: {}", idx);
             }
             match plane::path_to_exit_turning_right(
-                &alt_plane,
+                alt_plane,
                 &vec![OBSTACLE, NEW_OBSTACLE],
                 &guard,
             ) {
                 None => {
-                    // println!("Loop found");
-                    // matrix::pretty_print(alt_plane);
-                    // println!();
+                    trace!("Loop found with the obstacle in alt plane {}", idx);
                     1
                 }
                 _ => 0,
@@ -59,11 +58,22 @@ fn d6p2(file_path: &str) -> usize {
     // 1792 is too low!
 }
 
-pub fn d6() {
-    //let path = "inputs/d6sample.txt";
-    let path = "inputs/d6.txt";
-    let mut result = d6p1(path);
-    println!("Result Day 6 Part 1: {}", result);
-    result = d6p2(path);
-    println!("Result Day 6 Part 2: {}", result);
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        let plane = matrix::as_char_matrix_from_str(&input.text);
+        plane::check_unique_element(input, &plane, GUARD, "guard")?;
+        Ok(plane)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d6p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d6p2(input).into()
+    }
 }
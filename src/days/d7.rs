@@ -1,4 +1,6 @@
-use crate::utils;
+use crate::solution::{Answer, Solution};
+use crate::utils::{self, Line, ParseError, Source};
+use log::trace;
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 enum Operation {
@@ -7,26 +9,19 @@ enum Operation {
     Concatenate,
 }
 
-fn parse(input: String) -> (u64, Vec<u64>) {
-    let mut iter = input.split(':');
+fn parse(line: &Line) -> Result<(u64, Vec<u64>), ParseError> {
+    let (desired, operands) = line
+        .text
+        .split_once(':')
+        .ok_or_else(|| line.error("", "expected 'result: operands'"))?;
 
-    let desired_result = iter
-        .next()
-        .unwrap()
-        .parse()
-        .expect("Could not parse u64 desired_result");
-
-    let operands = iter
-        .next()
-        .unwrap()
+    let desired_result = line.parse(desired, "an integer result")?;
+    let operands = operands
         .split_whitespace()
-        .map(|x| {
-            x.parse()
-                .expect(format!("Could not parse u64 operand {}", x).as_str())
-        })
-        .collect();
+        .map(|x| line.parse(x, "an integer operand"))
+        .collect::<Result<_, _>>()?;
 
-    (desired_result, operands)
+    Ok((desired_result, operands))
 }
 
 fn result2(operation: &Operation, a: u64, b: u64) -> u64 {
@@ -89,11 +84,13 @@ fn has_solution(desired_result: &u64, operands: &Vec<u64>, use_concatenation: bo
             let operation_combinations =
                 generate_combinations(operands.len() - 1, use_concatenation);
             for operations in operation_combinations {
-                if *desired_result == result(&operations, &operands) {
-                    // println!(
-                    //     "Found solution: {} = {:?} using operations: {:?}",
-                    //     desired_result, operands, operations
-                    // );
+                if *desired_result == result(&operations, operands) {
+                    trace!(
+                        "Found solution: {} = {:?} using operations: {:?}",
+                        desired_result,
+                        operands,
+                        operations
+                    );
                     return true;
                 }
             }
@@ -102,35 +99,63 @@ fn has_solution(desired_result: &u64, operands: &Vec<u64>, use_concatenation: bo
     }
 }
 
-pub fn d7p1(file_path: &str) -> u64 {
-    utils::string_iter(file_path)
-        .map(parse)
+pub fn d7p1(equations: &Vec<(u64, Vec<u64>)>) -> u64 {
+    equations
+        .iter()
         .filter(|(desired, operands)| has_solution(desired, operands, false))
         .map(|(desired, _)| desired)
         .sum()
 }
 
-pub fn d7p2(file_path: &str) -> u64 {
-    utils::string_iter(file_path)
-        .map(parse)
+pub fn d7p2(equations: &Vec<(u64, Vec<u64>)>) -> u64 {
+    equations
+        .iter()
         .filter(|(desired, operands)| has_solution(desired, operands, true))
         .map(|(desired, _)| desired)
         .sum()
 }
 
-pub fn d7() {
-    //let file_path = "inputs/d7sample.txt";
-    let file_path = "inputs/d7.txt";
-    let mut result = d7p1(file_path);
-    println!("Result Day 7 Part 1: {}", result);
-    result = d7p2(file_path);
-    println!("Result Day 7 Part 2: {}", result);
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Vec<(u64, Vec<u64>)>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        input.lines().map(|line| parse(&line)).collect()
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d7p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d7p2(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse(&Line::new("test", 1, "190: 10 19")),
+            Ok((190, vec![10, 19]))
+        );
+        let err = parse(&Line::new("d7.txt", 2, "3267: 81 4o 27")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "d7.txt:2:10: expected an integer operand: '4o'"
+        );
+        assert_eq!(
+            parse(&Line::new("d7.txt", 3, "3267 81"))
+                .unwrap_err()
+                .column,
+            8
+        );
+    }
+
     #[test]
     fn result_2_test() {
         assert_eq!(result2(&Operation::Add, 2, 3), 5);
@@ -146,81 +171,71 @@ mod tests {
             vec![vec![Operation::Add], vec![Operation::Multiply]]
         );
         assert_eq!(
-            generate_combinations(2, false).sort(),
-            vec![
+            utils::sorted(generate_combinations(2, false)),
+            utils::sorted(vec![
                 vec![Operation::Add, Operation::Add],
                 vec![Operation::Add, Operation::Multiply],
                 vec![Operation::Multiply, Operation::Add],
-                vec![Operation::Multiply, Operation::Multiply],
-            ]
-            .sort()
+                vec![Operation::Multiply, Operation::Multiply]
+            ])
         );
     }
 
     #[test]
     fn result_test() {
-        assert_eq!(result(&vec![Operation::Add], &vec![2, 3]), 2 + 3);
-        assert_eq!(result(&vec![Operation::Multiply], &vec![2, 3]), 2 * 3);
+        assert_eq!(result(&[Operation::Add], &vec![2, 3]), 2 + 3);
+        assert_eq!(result(&[Operation::Multiply], &vec![2, 3]), 2 * 3);
 
         assert_eq!(
-            result(&vec![Operation::Add, Operation::Add], &vec![2, 3, 4]),
+            result(&[Operation::Add, Operation::Add], &vec![2, 3, 4]),
             2 + 3 + 4
         );
         assert_eq!(
-            result(&vec![Operation::Add, Operation::Multiply], &vec![2, 3, 4]),
+            result(&[Operation::Add, Operation::Multiply], &vec![2, 3, 4]),
             (2 + 3) * 4
         );
         assert_eq!(
-            result(&vec![Operation::Multiply, Operation::Add], &vec![2, 3, 4]),
+            result(&[Operation::Multiply, Operation::Add], &vec![2, 3, 4]),
             2 * 3 + 4
         );
         assert_eq!(
-            result(
-                &vec![Operation::Multiply, Operation::Multiply],
-                &vec![2, 3, 4]
-            ),
+            result(&[Operation::Multiply, Operation::Multiply], &vec![2, 3, 4]),
             2 * 3 * 4
         );
 
         assert_eq!(
             result(
-                &vec![Operation::Concatenate, Operation::Concatenate],
+                &[Operation::Concatenate, Operation::Concatenate],
                 &vec![2, 3, 4]
             ),
             234
         );
         assert_eq!(
-            result(
-                &vec![Operation::Add, Operation::Concatenate],
-                &vec![2, 3, 4]
-            ),
+            result(&[Operation::Add, Operation::Concatenate], &vec![2, 3, 4]),
             54
         );
         assert_eq!(
-            result(
-                &vec![Operation::Concatenate, Operation::Add],
-                &vec![2, 3, 4]
-            ),
+            result(&[Operation::Concatenate, Operation::Add], &vec![2, 3, 4]),
             23 + 4
         );
 
         assert_eq!(
             result(
-                &vec![Operation::Add, Operation::Add, Operation::Add],
+                &[Operation::Add, Operation::Add, Operation::Add],
                 &vec![2, 3, 4, 5]
             ),
             2 + 3 + 4 + 5
         );
         assert_eq!(
             result(
-                &vec![Operation::Multiply, Operation::Add, Operation::Multiply],
+                &[Operation::Multiply, Operation::Add, Operation::Multiply],
                 &vec![2, 3, 4, 5]
             ),
             (2 * 3 + 4) * 5
         );
         assert_eq!(
             result(
-                &vec![
+                &[
                     Operation::Multiply,
                     Operation::Multiply,
                     Operation::Multiply
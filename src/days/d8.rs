@@ -1,4 +1,5 @@
-use crate::utils::{self, matrix};
+use crate::solution::{Answer, Solution};
+use crate::utils::{self, matrix, ParseError, Source};
 use multimap::MultiMap;
 
 const EMPTY_SPACE: char = '.';
@@ -69,9 +70,8 @@ pub fn calc_all_antinodes(
         .collect()
 }
 
-pub fn d8p1(file_path: &str) -> usize {
-    let mat = matrix::as_char_matrix(file_path);
-    let antennas = parse_antennas(&mat);
+pub fn d8p1(mat: &Vec<Vec<char>>) -> usize {
+    let antennas = parse_antennas(mat);
     let antinodes: Vec<(usize, usize)> = antennas
         .iter_all()
         .map(|(_, v)| v.iter().map(|(a, b)| (*a as isize, *b as isize)).collect())
@@ -80,9 +80,8 @@ pub fn d8p1(file_path: &str) -> usize {
     matrix::unique_coordinates(&antinodes).len()
 }
 
-pub fn d8p2(file_path: &str) -> usize {
-    let mat = matrix::as_char_matrix(file_path);
-    let antennas = parse_antennas(&mat);
+pub fn d8p2(mat: &Vec<Vec<char>>) -> usize {
+    let antennas = parse_antennas(mat);
     let antinodes: Vec<(usize, usize)> = antennas
         .iter_all()
         .map(|(_, v)| v.iter().map(|(a, b)| (*a as isize, *b as isize)).collect())
@@ -91,13 +90,22 @@ pub fn d8p2(file_path: &str) -> usize {
     matrix::unique_coordinates(&antinodes).len()
 }
 
-pub fn d8() {
-    //let file_path = "inputs/d8sample.txt";
-    let file_path = "inputs/d8.txt";
-    let mut result = d8p1(file_path);
-    println!("Result Day 8 Part 1: {}", result);
-    result = d8p2(file_path);
-    println!("Result Day 8 Part 2: {}", result);
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Vec<Vec<char>>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        Ok(matrix::as_char_matrix_from_str(&input.text))
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d8p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d8p2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -125,28 +133,28 @@ mod tests {
         assert_eq!(calc_antinodes((0, 0), (1, 1), 2, 2, true), vec![]);
         assert_eq!(calc_antinodes((0, 0), (1, 1), 7, 7, true), vec![(2, 2)]);
         assert_eq!(
-            calc_antinodes((2, 2), (1, 1), 7, 7, true).sort(),
-            vec![(0, 0), (3, 3)].sort()
+            utils::sorted(calc_antinodes((2, 2), (1, 1), 7, 7, true)),
+            utils::sorted(vec![(0, 0), (3, 3)])
         );
     }
 
     #[test]
     fn antinodes_p2_test() {
         assert_eq!(
-            calc_antinodes((0, 0), (1, 1), 2, 2, false).sort(),
-            vec![(0, 0), (1, 1)].sort()
+            utils::sorted(calc_antinodes((0, 0), (1, 1), 2, 2, false)),
+            utils::sorted(vec![(0, 0), (1, 1)])
         );
         assert_eq!(
-            calc_antinodes((0, 0), (1, 1), 3, 3, false).sort(),
-            vec![(0, 0), (1, 1), (2, 2)].sort()
+            utils::sorted(calc_antinodes((0, 0), (1, 1), 3, 3, false)),
+            utils::sorted(vec![(0, 0), (1, 1), (2, 2)])
         );
         assert_eq!(
-            calc_antinodes((2, 2), (1, 1), 4, 4, false).sort(),
-            vec![(0, 0), (1, 1), (2, 2), (3, 3)].sort()
+            utils::sorted(calc_antinodes((2, 2), (1, 1), 4, 4, false)),
+            utils::sorted(vec![(0, 0), (1, 1), (2, 2), (3, 3)])
         );
         assert_eq!(
-            calc_antinodes((2, 2), (1, 1), 6, 7, false).sort(),
-            vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (5, 5)].sort()
+            utils::sorted(calc_antinodes((2, 2), (1, 1), 6, 7, false)),
+            utils::sorted(vec![(0, 0), (1, 1), (2, 2), (3, 3), (4, 4), (5, 5)])
         );
     }
 
@@ -161,13 +169,18 @@ mod tests {
             vec![(2, 2)]
         );
         assert_eq!(
-            calc_all_antinodes(&vec![(2, 2), (1, 1)], 4, 4, true).sort(),
-            vec![(0, 0), (3, 3)].sort()
+            utils::sorted(calc_all_antinodes(&vec![(2, 2), (1, 1)], 4, 4, true)),
+            utils::sorted(vec![(0, 0), (3, 3)])
         );
 
         assert_eq!(
-            calc_all_antinodes(&vec![(10, 10), (15, 15), (17, 11)], 22, 22, true).sort(),
-            vec![(5, 5), (20, 20), (19, 7), (13, 19), (3, 9)].sort()
+            utils::sorted(calc_all_antinodes(
+                &vec![(10, 10), (15, 15), (17, 11)],
+                22,
+                22,
+                true
+            )),
+            utils::sorted(vec![(5, 5), (20, 20), (19, 7), (13, 19), (3, 9)])
         );
     }
 }
@@ -1,15 +1,33 @@
-use crate::utils;
-use std::usize;
+use crate::solution::{Answer, Solution};
+use crate::utils::{ParseError, Source};
+use log::trace;
 
-fn to_fragmented(disk_map: String) -> Vec<Option<usize>> {
+// the disk map is a single line of digits, alternately the size of a file and of the gap after it
+fn parse(input: &Source) -> Result<Vec<u32>, ParseError> {
+    let mut disk_map = vec![];
+    for line in input.lines() {
+        if line.number > 1 && !line.text.is_empty() {
+            return Err(line.error("", "expected the disk map on a single line"));
+        }
+        for (idx, c) in line.text.char_indices() {
+            let size = c.to_digit(10).ok_or_else(|| {
+                line.error(&line.text[idx..idx + c.len_utf8()], "expected a digit")
+            })?;
+            disk_map.push(size);
+        }
+    }
+    if disk_map.iter().step_by(2).all(|&size| size == 0) {
+        return Err(input.error("expected a disk map with at least one file block"));
+    }
+    Ok(disk_map)
+}
+
+fn to_fragmented(disk_map: &[u32]) -> Vec<Option<usize>> {
     let mut result = vec![];
     let mut file_id = 0;
     let mut is_file = true;
 
-    for size in disk_map.chars().map(|c| {
-        c.to_digit(10)
-            .expect(format!("could not parse '{}' as digit", c).as_str())
-    }) {
+    for &size in disk_map {
         for _ in 0..size {
             result.push(if is_file { Some(file_id) } else { None });
         }
@@ -19,7 +37,7 @@ fn to_fragmented(disk_map: String) -> Vec<Option<usize>> {
         is_file = !is_file;
     }
 
-    //println!("fragmented -> {:?}", result);
+    trace!("fragmented -> {:?}", result);
     result
 }
 
@@ -44,7 +62,7 @@ fn defrag_p1(fragmented: Vec<Option<usize>>) -> Vec<Option<usize>> {
         }
     }
 
-    //println!("defragmented -> {:?}", result);
+    trace!("defragmented -> {:?}", result);
     result
 }
 
@@ -112,14 +130,14 @@ fn defrag_p2(fragmented: Vec<Option<usize>>) -> Vec<Option<usize>> {
             }
             // current file traversal ends because we're either in a gap or traversing a new file
             (_, Some(curr_file_id)) => {
-                //println!("pre move (idx={}): {:?}", source_frag_idx, defragmented);
+                trace!("pre move (idx={}): {:?}", source_frag_idx, defragmented);
                 maybe_move_file(
                     &mut defragmented,
                     curr_file_id,
                     current_file_len,
                     source_frag_idx + 1,
                 );
-                //println!("post move (idx={}): {:?}", source_frag_idx, defragmented);
+                trace!("post move (idx={}): {:?}", source_frag_idx, defragmented);
                 (current_file_id, current_file_len) = (None, 0);
             }
         }
@@ -136,43 +154,57 @@ fn checksum(defragged: Vec<Option<usize>>) -> usize {
         .sum()
 }
 
-pub fn d9p1(file_path: &str) -> usize {
-    let disk_map = utils::read_all(file_path);
+pub fn d9p1(disk_map: &[u32]) -> usize {
     let fragmented = to_fragmented(disk_map);
     let defragged = defrag_p1(fragmented);
     checksum(defragged)
 }
 
-pub fn d9p2(file_path: &str) -> usize {
-    let disk_map = utils::read_all(file_path);
+pub fn d9p2(disk_map: &[u32]) -> usize {
     let fragmented = to_fragmented(disk_map);
     let defragged = defrag_p2(fragmented);
     checksum(defragged)
 }
 
-pub fn d9() {
-    //let file_path = "inputs/d9sample1.txt";
-    //let file_path = "inputs/d9sample2.txt";
-    let file_path = "inputs/d9.txt";
-    let mut result = d9p1(file_path);
-    println!("Result Day 9 Part 1: {}", result);
-    result = d9p2(file_path);
-    println!("Result Day 9 Part 2: {}", result);
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = Vec<u32>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        parse(input)
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        d9p1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        d9p2(input).into()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn parse_test() {
+        assert_eq!(parse(&Source::new("ok", "123\n")), Ok(vec![1, 2, 3]));
+
+        let err = parse(&Source::new("bad", "12x3\n")).unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 3, "x"));
+        assert!(parse(&Source::new("two lines", "12\n34\n")).is_err());
+        assert!(parse(&Source::new("empty", "\n")).is_err());
+        assert!(parse(&Source::new("no files", "050\n")).is_err());
+    }
+
     #[test]
     fn to_fragmented_test() {
-        let mut disk_map = "1".to_string();
-        assert_eq!(to_fragmented(disk_map), vec![Some(0)]);
-        disk_map = "12".to_string();
-        assert_eq!(to_fragmented(disk_map), vec![Some(0), None, None]);
-        disk_map = "123".to_string();
+        assert_eq!(to_fragmented(&[1]), vec![Some(0)]);
+        assert_eq!(to_fragmented(&[1, 2]), vec![Some(0), None, None]);
         assert_eq!(
-            to_fragmented(disk_map),
+            to_fragmented(&[1, 2, 3]),
             vec![Some(0), None, None, Some(1), Some(1), Some(1)]
         );
     }
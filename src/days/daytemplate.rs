@@ -1,17 +1,30 @@
-pub fn dXp1(file_path: &str) -> usize {
+use crate::solution::{Answer, Solution};
+use crate::utils::{self, ParseError, Source};
+
+pub fn dXp1(_lines: &Vec<String>) -> usize {
     0
 }
 
-pub fn dXp2(file_path: &str) -> usize {
+pub fn dXp2(_lines: &Vec<String>) -> usize {
     0
 }
 
-pub fn dX() {
-    let file_path = "inputs/dXsample.txt";
-    let mut result = dXp1(file_path);
-    println!("Result Day X Part 1: {}", result);
-    result = dXp2(file_path);
-    println!("Result Day X Part 2: {}", result);
+pub struct DayX;
+
+impl Solution for DayX {
+    type Input = Vec<String>;
+
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+        Ok(utils::string_iter_from_str(&input.text).collect())
+    }
+
+    fn part1(&self, input: &Self::Input) -> Answer {
+        dXp1(input).into()
+    }
+
+    fn part2(&self, input: &Self::Input) -> Answer {
+        dXp2(input).into()
+    }
 }
 
 #[cfg(test)]
@@ -20,6 +33,6 @@ mod tests {
 
     #[test]
     fn test() {
-        assert!(true);
+        assert_eq!(dXp1(&vec![]), 0);
     }
-}
\ No newline at end of file
+}
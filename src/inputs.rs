@@ -0,0 +1,167 @@
+// finding input files by naming convention: inputs/d<day>.txt is the real input, and
+// inputs/d<day><name>.txt (e.g. d16sample1.txt) is the variant called <name>
+
+use std::fs;
+use std::path::Path;
+
+use advent_2024_rust::utils;
+
+pub const INPUTS_DIR: &str = "inputs";
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Input {
+    // None for the real input
+    pub name: Option<String>,
+    pub path: String,
+}
+
+impl Input {
+    pub fn label(&self) -> &str {
+        self.name.as_deref().unwrap_or("real")
+    }
+}
+
+// map an --input value to a file path
+pub fn path(day: u32, input: Option<&str>) -> String {
+    match input {
+        None => format!("{}/d{}.txt", INPUTS_DIR, day),
+        Some(utils::STDIN) => utils::STDIN.to_string(),
+        Some(path) if path.contains('/') || path.ends_with(".txt") => path.to_string(),
+        Some(name) => format!("{}/d{}{}.txt", INPUTS_DIR, day, name),
+    }
+}
+
+// "d16.txt" is day 16's real input and "d16sample1.txt" its sample1, but "d16.txt" is nothing to day 1
+fn variant_name(day: u32, file_name: &str) -> Option<&str> {
+    let name = file_name
+        .strip_prefix(&format!("d{}", day))?
+        .strip_suffix(".txt")?;
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        None
+    } else {
+        Some(name)
+    }
+}
+
+// every input for the day found in dir, real input first and then variants by name
+pub fn discover_in(dir: &str, day: u32) -> Vec<Input> {
+    let mut found: Vec<Input> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let file_name = entry.file_name().into_string().ok()?;
+                let name = variant_name(day, &file_name)?;
+                Some(Input {
+                    name: if name.is_empty() {
+                        None
+                    } else {
+                        Some(name.to_string())
+                    },
+                    path: format!("{}/{}", dir, file_name),
+                })
+            })
+            .collect(),
+        Err(_) => vec![],
+    };
+    found.sort_by(|a, b| a.name.cmp(&b.name));
+    found
+}
+
+pub fn discover(day: u32) -> Vec<Input> {
+    discover_in(INPUTS_DIR, day)
+}
+
+// the path for an --input value, or an explanation of what is available if it doesn't exist
+pub fn resolve(day: u32, input: Option<&str>) -> Result<String, String> {
+    let path = path(day, input);
+    if path == utils::STDIN || Path::new(&path).is_file() {
+        return Ok(path);
+    }
+
+    let available = discover(day);
+    if available.is_empty() {
+        Err(format!(
+            "{} not found, and there are no inputs for day {} in {}/",
+            path, day, INPUTS_DIR
+        ))
+    } else {
+        let labels: Vec<&str> = available.iter().map(Input::label).collect();
+        Err(format!(
+            "{} not found; inputs for day {}: {}",
+            path,
+            day,
+            labels.join(", ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_2024_rust::testing::temp_path;
+    use std::path::PathBuf;
+
+    #[test]
+    fn path_test() {
+        assert_eq!(path(16, None), "inputs/d16.txt");
+        assert_eq!(path(16, Some("sample1")), "inputs/d16sample1.txt");
+        assert_eq!(path(16, Some("/tmp/mine.txt")), "/tmp/mine.txt");
+        assert_eq!(path(16, Some("mine.txt")), "mine.txt");
+        assert_eq!(path(16, Some("-")), "-");
+    }
+
+    #[test]
+    fn variant_name_test() {
+        assert_eq!(variant_name(16, "d16.txt"), Some(""));
+        assert_eq!(variant_name(16, "d16sample1.txt"), Some("sample1"));
+        assert_eq!(variant_name(1, "d16.txt"), None);
+        assert_eq!(variant_name(1, "d1sample.txt"), Some("sample"));
+        assert_eq!(variant_name(16, "d16.txt~"), None);
+        assert_eq!(variant_name(16, "answers.txt"), None);
+    }
+
+    #[test]
+    fn discover_test() {
+        let dir = PathBuf::from(temp_path("inputs_discover_test"));
+        fs::create_dir_all(&dir).unwrap();
+        for file in [
+            "d16.txt",
+            "d16sample2.txt",
+            "d16sample1.txt",
+            "d1.txt",
+            "notes.txt",
+        ] {
+            fs::write(dir.join(file), "").unwrap();
+        }
+        let dir_str = dir.to_str().unwrap();
+
+        let labels: Vec<String> = discover_in(dir_str, 16)
+            .iter()
+            .map(|i| i.label().to_string())
+            .collect();
+        assert_eq!(labels, vec!["real", "sample1", "sample2"]);
+        assert_eq!(
+            discover_in(dir_str, 16)[1].path,
+            format!("{}/d16sample1.txt", dir_str)
+        );
+        assert_eq!(discover_in(dir_str, 1).len(), 1);
+        assert_eq!(discover_in(dir_str, 11), vec![]);
+        assert_eq!(discover_in("does/not/exist", 16), vec![]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn resolve_test() {
+        assert_eq!(
+            resolve(16, Some("sample1")),
+            Ok("inputs/d16sample1.txt".to_string())
+        );
+        let err = resolve(16, Some("sample9")).unwrap_err();
+        assert!(err.contains("inputs/d16sample9.txt not found"), "{}", err);
+        assert!(err.contains("sample1, sample2"), "{}", err);
+        assert!(resolve(99, None)
+            .unwrap_err()
+            .contains("no inputs for day 99"));
+    }
+}
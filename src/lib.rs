@@ -0,0 +1,26 @@
+//! Solutions to Advent of Code 2024, and the input parsing, grid and pathfinding helpers they are
+//! built on.
+//!
+//! Every day implements [`solution::Solution`], and [`days::registry`] lists them all behind the
+//! object-safe [`solution::Solver`], so a day can be solved from any [`utils::Source`]:
+//!
+//! ```
+//! use advent_2024_rust::days;
+//! use advent_2024_rust::solution::Answer;
+//! use advent_2024_rust::utils::Source;
+//!
+//! let day = days::find(1).unwrap();
+//! let input = Source::new("example", "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n");
+//! assert_eq!(day.solver.solve_part(&input, 1), Ok(Answer::Int(11)));
+//! ```
+//!
+//! The helpers in [`utils`], [`utils::matrix`] and [`utils::plane`] work on plain
+//! `Vec<Vec<T>>` grids indexed by row and then column.
+
+#![allow(clippy::ptr_arg)]
+
+pub mod days;
+pub mod solution;
+#[doc(hidden)]
+pub mod testing;
+pub mod utils;
@@ -0,0 +1,153 @@
+// diagnostics from the solvers go through the log macros and end up on stderr, so results on stdout stay clean.
+// Quiet shows errors only, the default adds warnings, -v adds debug and -vv adds trace.
+// Messages from a day's module can be limited to chosen days; everything else is always shown.
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Settings {
+    pub level: LevelFilter,
+    // empty means every day
+    pub days: Vec<u32>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            level: LevelFilter::Warn,
+            days: vec![],
+        }
+    }
+}
+
+// the day a log target belongs to, e.g. 3 for "advent_2024_rust::days::d3"
+fn target_day(target: &str) -> Option<u32> {
+    let (_, module) = target.split_once("days::d")?;
+    module.split("::").next()?.parse().ok()
+}
+
+struct Logger {
+    settings: Settings,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        if metadata.level() > self.settings.level {
+            return false;
+        }
+        match target_day(metadata.target()) {
+            Some(day) => self.settings.days.is_empty() || self.settings.days.contains(&day),
+            None => true,
+        }
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let source = match target_day(record.target()) {
+            Some(day) => format!("d{}", day),
+            None => record
+                .target()
+                .rsplit("::")
+                .next()
+                .unwrap_or("")
+                .to_string(),
+        };
+        eprintln!("[{} {}] {}", record.level(), source, record.args());
+    }
+
+    fn flush(&self) {}
+}
+
+pub fn init(settings: Settings) {
+    log::set_max_level(settings.level);
+    // only the first call can install a logger, which is all the binary needs
+    let _ = log::set_boxed_logger(Box::new(Logger { settings }));
+}
+
+// remove the logging flags (-q/--quiet, -v, -vv, --log-day <days>) from anywhere in the arguments
+pub fn parse_args(args: &[String]) -> Result<(Settings, Vec<String>), String> {
+    let mut settings = Settings::default();
+    let mut rest = vec![];
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-q" | "--quiet" => settings.level = LevelFilter::Error,
+            "-v" | "--verbose" => settings.level = LevelFilter::Debug,
+            "-vv" => settings.level = LevelFilter::Trace,
+            "--log-day" => {
+                let days = iter.next().ok_or("--log-day requires a value")?;
+                for day in days.split(',') {
+                    settings.days.push(day.parse().map_err(|_| {
+                        format!("--log-day expects days like 3,10, got '{}'", days)
+                    })?);
+                }
+            }
+            _ => rest.push(arg.clone()),
+        }
+    }
+    Ok((settings, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn target_day_test() {
+        assert_eq!(target_day("advent_2024_rust::days::d3"), Some(3));
+        assert_eq!(target_day("advent_2024_rust::days::d21::inner"), Some(21));
+        assert_eq!(target_day("advent_2024_rust::utils::matrix"), None);
+        assert_eq!(target_day("advent_2024_rust::days"), None);
+    }
+
+    #[test]
+    fn parse_args_test() {
+        assert_eq!(
+            parse_args(&args("run 3 -vv --log-day 3,10")),
+            Ok((
+                Settings {
+                    level: LevelFilter::Trace,
+                    days: vec![3, 10]
+                },
+                args("run 3")
+            ))
+        );
+        assert_eq!(
+            parse_args(&args("-q list")),
+            Ok((
+                Settings {
+                    level: LevelFilter::Error,
+                    days: vec![]
+                },
+                args("list")
+            ))
+        );
+        assert_eq!(
+            parse_args(&args("run 3")),
+            Ok((Settings::default(), args("run 3")))
+        );
+        assert!(parse_args(&args("run 3 --log-day")).is_err());
+        assert!(parse_args(&args("run 3 --log-day three")).is_err());
+    }
+
+    #[test]
+    fn enabled_test() {
+        let logger = Logger {
+            settings: Settings {
+                level: LevelFilter::Debug,
+                days: vec![3],
+            },
+        };
+        let metadata = |level, target| Metadata::builder().level(level).target(target).build();
+        assert!(logger.enabled(&metadata(log::Level::Debug, "advent_2024_rust::days::d3")));
+        assert!(!logger.enabled(&metadata(log::Level::Trace, "advent_2024_rust::days::d3")));
+        assert!(!logger.enabled(&metadata(log::Level::Debug, "advent_2024_rust::days::d10")));
+        assert!(logger.enabled(&metadata(log::Level::Debug, "advent_2024_rust::cli")));
+    }
+}
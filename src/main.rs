@@ -1,28 +1,43 @@
-#![allow(dead_code)]
+#![allow(clippy::ptr_arg)]
 
-mod days;
-mod utils;
+mod answers;
+mod aoc;
+mod bench;
+mod bounds;
+mod cli;
+mod inputs;
+mod logging;
+mod parallel;
+mod records;
+mod scaffold;
+mod submit;
 
-fn main() {
-    // days::d1::d1();
-    // days::d2::d2();
-    // days::d3::d3();
-    // days::d4::d4();
-    // days::d5::d5();
-    // days::d6::d6();  // slow - can I improve it?
-    // days::d7::d7();  // slow - can I improve it?
-    // days::d8::d8();
-    // days::d9::d9();
-    // days::d10::d10();
-    // days::d11::d11();
-    // days::d12::d12();
-    // days::d13::d13();
-    // days::d14::d14();
-    // days::d15::d15();
-    // days::d16::d16();
-    // days::d17::d17();
-    days::d18::d18();
-    // days::d19::d19();
-    // days::d20::d20();
-    // days::d21::d21();   // slow, did not attempt part 2 - come back to this sometime
+use std::env;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (log_settings, args) = match logging::parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    logging::init(log_settings);
+
+    let command = match cli::parse_args(&args) {
+        Ok(command) => command,
+        Err(message) => {
+            eprintln!("error: {}\n\n{}", message, cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    match cli::execute(command) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("error: {}", message);
+            ExitCode::FAILURE
+        }
+    }
 }
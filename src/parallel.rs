@@ -0,0 +1,197 @@
+// running many days and parts at once: each (day, part) is a job taken from a shared queue by a fixed
+// number of worker threads. A job that panics is reported as failed, and the others carry on.
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use advent_2024_rust::days::Day;
+use advent_2024_rust::solution::Answer;
+use advent_2024_rust::utils::Source;
+
+use crate::bench;
+
+pub struct Job {
+    pub day: Day,
+    pub part: u32,
+    pub path: String,
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Report {
+    pub day: u32,
+    pub part: u32,
+    // the answer, or why there isn't one
+    pub result: Result<Answer, String>,
+    pub elapsed: Duration,
+}
+
+// what was passed to panic!, which is almost always a &str or a String
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+fn run_job(job: &Job) -> Report {
+    let start = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        Source::read(&job.path).and_then(|input| job.day.solver.solve_part(&input, job.part))
+    }));
+    let elapsed = start.elapsed();
+    Report {
+        day: job.day.day,
+        part: job.part,
+        result: match result {
+            Ok(Ok(answer)) => Ok(answer),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(payload) => Err(format!("panicked: {}", panic_message(payload.as_ref()))),
+        },
+        elapsed,
+    }
+}
+
+// run every job on up to `threads` threads, returning the reports sorted by day and then part
+pub fn run(jobs: &Vec<Job>, threads: usize) -> Vec<Report> {
+    let next = AtomicUsize::new(0);
+    let reports = Mutex::new(vec![]);
+    thread::scope(|scope| {
+        for _ in 0..threads.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| {
+                while let Some(job) = jobs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let report = run_job(job);
+                    // jobs can't panic while holding the lock, so it is never poisoned
+                    reports.lock().unwrap().push(report);
+                }
+            });
+        }
+    });
+
+    let mut reports = reports.into_inner().unwrap();
+    reports.sort_by_key(|r| (r.day, r.part));
+    reports
+}
+
+pub fn format_table(reports: &Vec<Report>) -> Vec<String> {
+    let mut lines = vec!["Day Part        Time  Answer".to_string()];
+    for r in reports {
+        let answer = match &r.result {
+            Ok(answer) => answer.to_string(),
+            Err(message) => format!("FAILED: {}", message),
+        };
+        lines.push(format!(
+            "{:>3} {:>4} {:>11}  {}",
+            r.day,
+            r.part,
+            bench::format_duration(r.elapsed),
+            answer
+        ));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_2024_rust::solution::Solution;
+    use advent_2024_rust::testing::temp_path;
+    use advent_2024_rust::utils::ParseError;
+
+    // answers with the length of its input, or panics on part 2 if the input is empty
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Input = usize;
+
+        fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+            Ok(input.text.len())
+        }
+
+        fn part1(&self, input: &Self::Input) -> Answer {
+            Answer::from(*input)
+        }
+
+        fn part2(&self, input: &Self::Input) -> Answer {
+            Answer::from(100 / *input)
+        }
+    }
+
+    fn job(day: u32, part: u32, path: &str) -> Job {
+        Job {
+            day: Day {
+                day,
+                solver: &Fragile,
+                note: None,
+            },
+            part,
+            path: path.to_string(),
+        }
+    }
+
+    #[test]
+    fn run_test() {
+        let path = &temp_path("parallel_run_test.txt");
+        std::fs::write(path, "").unwrap();
+
+        let jobs = vec![
+            job(2, 2, path),
+            job(1, 1, "Cargo.toml"),
+            job(2, 1, path),
+            job(3, 1, "does/not/exist.txt"),
+        ];
+        let reports = run(&jobs, 3);
+        std::fs::remove_file(path).unwrap();
+
+        let results: Vec<(u32, u32, &Result<Answer, String>)> =
+            reports.iter().map(|r| (r.day, r.part, &r.result)).collect();
+        let cargo_toml_len = std::fs::read_to_string("Cargo.toml").unwrap().len();
+        assert_eq!(results[0], (1, 1, &Ok(Answer::from(cargo_toml_len))));
+        assert_eq!(results[1], (2, 1, &Ok(Answer::Int(0))));
+        assert_eq!(
+            results[2],
+            (
+                2,
+                2,
+                &Err("panicked: attempt to divide by zero".to_string())
+            )
+        );
+        assert!(reports[3]
+            .result
+            .as_ref()
+            .unwrap_err()
+            .starts_with("does/not/exist.txt: could not read"));
+    }
+
+    #[test]
+    fn format_table_test() {
+        let reports = vec![
+            Report {
+                day: 6,
+                part: 2,
+                result: Ok(Answer::Int(1933)),
+                elapsed: Duration::from_millis(1500),
+            },
+            Report {
+                day: 17,
+                part: 1,
+                result: Err("panicked: oops".to_string()),
+                elapsed: Duration::from_micros(20),
+            },
+        ];
+        assert_eq!(
+            format_table(&reports),
+            vec![
+                "Day Part        Time  Answer",
+                "  6    2      1.500s  1933",
+                " 17    1        20us  FAILED: panicked: oops",
+            ]
+        );
+    }
+}
@@ -0,0 +1,79 @@
+// the plain text files answers, bounds, timings and guesses are kept in share one layout:
+// a record per line with whitespace-separated fields, skipping blank lines and # comments
+
+use std::fs;
+use std::path::Path;
+
+// split a record into exactly the fields the layout names, e.g. "day part input answer"
+pub fn fields<'a>(line: &'a str, layout: &str) -> Result<Vec<&'a str>, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    if fields.len() != layout.split_whitespace().count() {
+        return Err(format!("expected '{}', got '{}'", layout, line));
+    }
+    Ok(fields)
+}
+
+// the day and part from the first two fields, which every record starts with
+pub fn day_part(fields: &[&str], line: &str) -> Result<(u32, u32), String> {
+    let day = fields[0]
+        .parse()
+        .map_err(|_| format!("invalid day '{}' in '{}'", fields[0], line))?;
+    let part = fields[1]
+        .parse()
+        .map_err(|_| format!("invalid part '{}' in '{}'", fields[1], line))?;
+    Ok((day, part))
+}
+
+pub fn parse<T>(
+    contents: &str,
+    parse_record: impl Fn(&str) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    let mut records = vec![];
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        records.push(parse_record(line).map_err(|e| format!("line {}: {}", idx + 1, e))?);
+    }
+    Ok(records)
+}
+
+// a missing file just means nothing has been recorded yet
+pub fn load<T>(
+    path: &str,
+    parse_record: impl Fn(&str) -> Result<T, String>,
+) -> Result<Vec<T>, String> {
+    if !Path::new(path).exists() {
+        return Ok(vec![]);
+    }
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+    parse(&contents, parse_record).map_err(|e| format!("{} {}", path, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_pair(line: &str) -> Result<(u32, u32), String> {
+        day_part(&fields(line, "day part")?, line)
+    }
+
+    #[test]
+    fn parse_test() {
+        assert_eq!(
+            parse("# day part\n\n1 2\n  17 1  \n", parse_pair),
+            Ok(vec![(1, 2), (17, 1)])
+        );
+        assert_eq!(
+            parse("1 2\n1 2 3\n", parse_pair),
+            Err("line 2: expected 'day part', got '1 2 3'".to_string())
+        );
+        assert_eq!(
+            parse("x 2", parse_pair),
+            Err("line 1: invalid day 'x' in 'x 2'".to_string())
+        );
+        assert_eq!(load("does/not/exist.txt", parse_pair), Ok(vec![]));
+    }
+}
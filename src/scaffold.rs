@@ -0,0 +1,205 @@
+// generating a new day from src/days/daytemplate.rs: the module, its entry in days.rs and empty inputs
+
+use std::fs;
+use std::path::Path;
+
+use crate::inputs;
+
+const TEMPLATE_PATH: &str = "src/days/daytemplate.rs";
+const DAYS_PATH: &str = "src/days.rs";
+
+// the template's names are all dX... (functions) or DayX (the solution)
+pub fn render_template(template: &str, day: u32) -> String {
+    template
+        .replace("dX", &format!("d{}", day))
+        .replace("DayX", &format!("Day{}", day))
+}
+
+fn registry_entry(day: u32) -> Vec<String> {
+    vec![
+        "        Day {".to_string(),
+        format!("            day: {},", day),
+        format!("            solver: &d{}::Day{},", day, day),
+        "            note: Some(\"not implemented yet\"),".to_string(),
+        "        },".to_string(),
+    ]
+}
+
+// add `pub mod dN;` and a registry entry to the contents of days.rs, keeping both in order
+pub fn register(days_rs: &str, day: u32) -> Result<String, String> {
+    let module = format!("d{}", day);
+    let mod_line = format!("pub mod {};", module);
+    let mut lines: Vec<String> = days_rs.lines().map(str::to_string).collect();
+    if lines.contains(&mod_line) {
+        return Err(format!("day {} is already declared in {}", day, DAYS_PATH));
+    }
+
+    // modules are sorted by name the way rustfmt sorts them, so d10 comes before d2
+    let mod_lines: Vec<usize> = (0..lines.len())
+        .filter(|&i| lines[i].starts_with("pub mod "))
+        .collect();
+    let last_mod = *mod_lines
+        .last()
+        .ok_or(format!("no 'pub mod' lines in {}", DAYS_PATH))?;
+    let mod_idx = mod_lines
+        .iter()
+        .copied()
+        .find(|&i| {
+            lines[i]
+                .trim_start_matches("pub mod ")
+                .trim_end_matches(';')
+                > module.as_str()
+        })
+        .unwrap_or(last_mod + 1);
+    lines.insert(mod_idx, mod_line);
+
+    // registry entries are in day order, and each starts on the line before its `day: N,`
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("pub fn registry"))
+        .ok_or(format!("no registry() in {}", DAYS_PATH))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|l| l.trim() == "]")
+            .ok_or(format!(
+                "could not find the end of registry() in {}",
+                DAYS_PATH
+            ))?;
+    let later_day = (start..end).find(|&i| {
+        lines[i]
+            .trim()
+            .strip_prefix("day: ")
+            .and_then(|d| d.trim_end_matches(',').parse::<u32>().ok())
+            .is_some_and(|d| d > day)
+    });
+    let entry_idx = later_day.map(|i| i - 1).unwrap_or(end);
+    for (offset, line) in registry_entry(day).into_iter().enumerate() {
+        lines.insert(entry_idx + offset, line);
+    }
+
+    Ok(lines.join("\n") + "\n")
+}
+
+// create everything for a new day under root (the crate directory), returning the files created
+pub fn new_day(root: &Path, day: u32) -> Result<Vec<String>, String> {
+    if !(1..=25).contains(&day) {
+        return Err(format!("there is no day {}, advent runs from 1 to 25", day));
+    }
+    let module_path = root.join(format!("src/days/d{}.rs", day));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))
+    };
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents).map_err(|e| format!("could not write {}: {}", path.display(), e))
+    };
+
+    let template = read(&root.join(TEMPLATE_PATH))?;
+    let days_path = root.join(DAYS_PATH);
+    let days_rs = register(&read(&days_path)?, day)?;
+
+    let mut created = vec![];
+    write(&module_path, &render_template(&template, day))?;
+    created.push(module_path.display().to_string());
+    write(&days_path, &days_rs)?;
+
+    for name in [None, Some("sample")] {
+        let input_path = root.join(inputs::path(day, name));
+        if !input_path.exists() {
+            write(&input_path, "")?;
+            created.push(input_path.display().to_string());
+        }
+    }
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use advent_2024_rust::testing::temp_path;
+    use std::path::PathBuf;
+
+    const DAYS_RS: &str = "pub mod d1;
+pub mod d3;
+
+pub fn registry() -> Vec<Day> {
+    vec![
+        Day {
+            day: 1,
+            solver: &d1::Day1,
+            note: None,
+        },
+        Day {
+            day: 3,
+            solver: &d3::Day3,
+            note: None,
+        },
+    ]
+}
+";
+
+    #[test]
+    fn render_template_test() {
+        let template = "pub fn dXp1() {}\npub struct DayX;\nimpl Solution for DayX {}\n";
+        assert_eq!(
+            render_template(template, 18),
+            "pub fn d18p1() {}\npub struct Day18;\nimpl Solution for Day18 {}\n"
+        );
+    }
+
+    #[test]
+    fn register_test() {
+        let days_rs = register(DAYS_RS, 2).unwrap();
+        let days_rs = register(&days_rs, 10).unwrap();
+        let mods: Vec<&str> = days_rs
+            .lines()
+            .filter(|l| l.starts_with("pub mod"))
+            .collect();
+        assert_eq!(
+            mods,
+            vec!["pub mod d1;", "pub mod d10;", "pub mod d2;", "pub mod d3;"]
+        );
+        let days: Vec<&str> = days_rs
+            .lines()
+            .filter(|l| l.trim().starts_with("day: "))
+            .map(str::trim)
+            .collect();
+        assert_eq!(days, vec!["day: 1,", "day: 2,", "day: 3,", "day: 10,"]);
+        assert!(days_rs.contains("            solver: &d2::Day2,\n"));
+
+        assert!(register(DAYS_RS, 3).is_err());
+    }
+
+    #[test]
+    fn new_day_test() {
+        let root = PathBuf::from(temp_path("scaffold_new_day_test"));
+        fs::create_dir_all(root.join("src/days")).unwrap();
+        fs::create_dir_all(root.join("inputs")).unwrap();
+        fs::write(root.join(TEMPLATE_PATH), "pub struct DayX;\n").unwrap();
+        fs::write(root.join(DAYS_PATH), DAYS_RS).unwrap();
+        fs::write(root.join("inputs/d2sample.txt"), "keep me").unwrap();
+
+        let created = new_day(&root, 2).unwrap();
+        assert_eq!(created.len(), 2);
+        assert_eq!(
+            fs::read_to_string(root.join("src/days/d2.rs")).unwrap(),
+            "pub struct Day2;\n"
+        );
+        assert!(fs::read_to_string(root.join(DAYS_PATH))
+            .unwrap()
+            .contains("pub mod d2;"));
+        assert_eq!(fs::read_to_string(root.join("inputs/d2.txt")).unwrap(), "");
+        assert_eq!(
+            fs::read_to_string(root.join("inputs/d2sample.txt")).unwrap(),
+            "keep me"
+        );
+
+        assert!(new_day(&root, 2).is_err());
+        assert!(new_day(&root, 26).is_err());
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,177 @@
+//! The common shape of every day: parse the input once, then answer both parts from it.
+
+use std::fmt::{self, Display};
+
+use crate::utils::{ParseError, Source};
+
+/// An answer to one part of a day, printed the way the puzzle site expects it.
+#[derive(PartialEq, Eq, Debug, Clone, Hash)]
+pub enum Answer {
+    Int(i64),
+    BigInt(u128),
+    Str(String),
+    /// There is no answer for this input, and the reason why. It prints as `no solution: ...`,
+    /// which has spaces in it, so it can't be recorded or submitted as an answer.
+    Unsolved(String),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            Answer::BigInt(n) => write!(f, "{}", n),
+            Answer::Str(s) => write!(f, "{}", s),
+            Answer::Unsolved(reason) => write!(f, "no solution: {}", reason),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(n: i32) -> Self {
+        Answer::Int(n as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Answer::Int(n as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(n: i64) -> Self {
+        Answer::Int(n)
+    }
+}
+
+// unsigned answers only need a big int when they don't fit in an i64
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(n as u128),
+        }
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Answer::from(n as u64)
+    }
+}
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i64::try_from(n) {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::BigInt(n),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        Answer::Str(s.to_string())
+    }
+}
+
+/// A day's puzzle: parse the input once, then answer each part from it.
+pub trait Solution {
+    type Input;
+
+    /// Turn the raw input into the form both parts work from.
+    fn parse(&self, input: &Source) -> Result<Self::Input, ParseError>;
+    /// The answer to the first part.
+    fn part1(&self, input: &Self::Input) -> Answer;
+    /// The answer to the second part.
+    fn part2(&self, input: &Self::Input) -> Answer;
+}
+
+/// An object-safe view of a [`Solution`], so that days with different `Input` types can share a registry.
+pub trait Solver: Sync {
+    /// Parse the input and answer one part, which must be 1 or 2.
+    fn solve_part(&self, input: &Source, part: u32) -> Result<Answer, ParseError>;
+    /// Parse the input once and answer both parts.
+    fn solve(&self, input: &Source) -> Result<(Answer, Answer), ParseError>;
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn solve_part(&self, input: &Source, part: u32) -> Result<Answer, ParseError> {
+        let input = self.parse(input)?;
+        match part {
+            1 => Ok(self.part1(&input)),
+            2 => Ok(self.part2(&input)),
+            _ => panic!("there is no part {}", part),
+        }
+    }
+
+    fn solve(&self, input: &Source) -> Result<(Answer, Answer), ParseError> {
+        let input = self.parse(input)?;
+        Ok((self.part1(&input), self.part2(&input)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answer_from_test() {
+        assert_eq!(Answer::from(-3_i32), Answer::Int(-3));
+        assert_eq!(Answer::from(7_usize), Answer::Int(7));
+        assert_eq!(Answer::from(u64::MAX), Answer::BigInt(u64::MAX as u128));
+        assert_eq!(Answer::from(5_u128), Answer::Int(5));
+        assert_eq!(Answer::from("4,6,3"), Answer::Str("4,6,3".to_string()));
+    }
+
+    #[test]
+    fn answer_display_test() {
+        assert_eq!(Answer::Int(-2487).to_string(), "-2487");
+        assert_eq!(Answer::BigInt(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(Answer::from("4,6,3").to_string(), "4,6,3");
+        assert_eq!(
+            Answer::Unsolved("the exit is never cut off".to_string()).to_string(),
+            "no solution: the exit is never cut off"
+        );
+    }
+
+    struct Doubler;
+
+    impl Solution for Doubler {
+        type Input = u32;
+
+        fn parse(&self, input: &Source) -> Result<Self::Input, ParseError> {
+            match input.text.as_str() {
+                "" => Err(input.error("no input")),
+                text => Ok(text.len() as u32),
+            }
+        }
+
+        fn part1(&self, input: &Self::Input) -> Answer {
+            Answer::from(*input)
+        }
+
+        fn part2(&self, input: &Self::Input) -> Answer {
+            Answer::from(input * 2)
+        }
+    }
+
+    #[test]
+    fn solver_test() {
+        let solver: &dyn Solver = &Doubler;
+        let input = |text| Source::new("test", text);
+        assert_eq!(solver.solve_part(&input("abc"), 1), Ok(Answer::Int(3)));
+        assert_eq!(solver.solve_part(&input("abc"), 2), Ok(Answer::Int(6)));
+        assert_eq!(
+            solver.solve(&input("ab")),
+            Ok((Answer::Int(2), Answer::Int(4)))
+        );
+        assert!(solver.solve(&input("")).is_err());
+    }
+}
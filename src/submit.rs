@@ -0,0 +1,294 @@
+// submitting answers, with a log of every guess so a known-wrong answer is never sent twice:
+//
+// # day part answer verdict
+// 6 2 1792 too_low
+//
+// verdicts are correct, too_high, too_low or wrong (wrong with no hint either way)
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use crate::aoc::{self, Config};
+use crate::records;
+
+pub const GUESSES_PATH: &str = "inputs/guesses.txt";
+const LAYOUT: &str = "day part answer verdict";
+
+#[derive(PartialEq, Debug, Clone)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    // submitted too soon after the last guess; holds the site's "You have ... left to wait" text
+    Wait(String),
+    // the part has already been solved, so the site didn't check the answer
+    AlreadySolved,
+    Unrecognised(String),
+}
+
+impl Verdict {
+    fn log_name(&self) -> Option<&'static str> {
+        match self {
+            Verdict::Correct => Some("correct"),
+            Verdict::TooHigh => Some("too_high"),
+            Verdict::TooLow => Some("too_low"),
+            Verdict::Wrong => Some("wrong"),
+            _ => None,
+        }
+    }
+
+    fn from_log_name(name: &str) -> Option<Verdict> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Guess {
+    pub day: u32,
+    pub part: u32,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+// the site replies with a whole HTML page; only the <article> text matters
+fn article_text(html: &str) -> String {
+    let article = match (html.find("<article"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in article.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+pub fn parse_response(html: &str) -> Verdict {
+    let text = article_text(html);
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Wrong
+        }
+    } else if text.contains("You gave an answer too recently") {
+        let wait = text
+            .find("You have ")
+            .map(|idx| text[idx..].split(" left to wait").next().unwrap_or(""))
+            .unwrap_or("");
+        Verdict::Wait(wait.to_string())
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::AlreadySolved
+    } else {
+        Verdict::Unrecognised(text)
+    }
+}
+
+fn parse_record(line: &str) -> Result<Guess, String> {
+    let fields = records::fields(line, LAYOUT)?;
+    let (day, part) = records::day_part(&fields, line)?;
+    Ok(Guess {
+        day,
+        part,
+        answer: fields[2].to_string(),
+        verdict: Verdict::from_log_name(fields[3])
+            .ok_or(format!("invalid verdict '{}' in '{}'", fields[3], line))?,
+    })
+}
+
+pub fn load(path: &str) -> Result<Vec<Guess>, String> {
+    records::load(path, parse_record)
+}
+
+// only definite verdicts are logged; waiting or an unreadable reply says nothing about the answer
+pub fn log(path: &str, guess: &Guess) -> Result<(), String> {
+    let verdict = match guess.verdict.log_name() {
+        Some(verdict) => verdict,
+        None => return Ok(()),
+    };
+    let mut contents = String::new();
+    if !Path::new(path).exists() {
+        contents.push_str(&format!("# {}\n", LAYOUT));
+    }
+    contents.push_str(&format!(
+        "{} {} {} {}\n",
+        guess.day, guess.part, guess.answer, verdict
+    ));
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .map_err(|e| format!("could not write {}: {}", path, e))
+}
+
+// an explanation of why this answer shouldn't be sent, going by earlier guesses
+pub fn refusal(guesses: &Vec<Guess>, day: u32, part: u32, answer: &str) -> Option<String> {
+    let earlier = guesses.iter().filter(|g| g.day == day && g.part == part);
+    for guess in earlier {
+        if guess.verdict == Verdict::Correct {
+            return Some(format!(
+                "day {} part {} was already solved with {}",
+                day, part, guess.answer
+            ));
+        }
+        if guess.answer == answer {
+            return Some(format!(
+                "{} was already submitted for day {} part {} and was {}",
+                answer,
+                day,
+                part,
+                guess
+                    .verdict
+                    .log_name()
+                    .unwrap_or("wrong")
+                    .replace('_', " ")
+            ));
+        }
+    }
+    None
+}
+
+// post the answer unless the log says it can't be right, then log the verdict
+pub fn submit(
+    config: &Config,
+    log_path: &str,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Verdict, String> {
+    if answer.is_empty() || answer.contains(char::is_whitespace) {
+        return Err(format!("answer '{}' must be a single word", answer));
+    }
+    if let Some(reason) = refusal(&load(log_path)?, day, part, answer) {
+        return Err(format!("not submitting: {}", reason));
+    }
+
+    let url = format!("{}/answer", config.day_url(day));
+    let level = part.to_string();
+    let html = aoc::post_form(config, &url, &[("level", &level), ("answer", answer)])?;
+    let verdict = parse_response(&html);
+    log(
+        log_path,
+        &Guess {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict: verdict.clone(),
+        },
+    )?;
+    Ok(verdict)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aoc::tests::{stub_config, stub_server};
+    use advent_2024_rust::testing::temp_path;
+    use std::fs;
+
+    const TOO_LOW: &str =
+        "<html><main><article><p>That's not the right answer; your answer is too low. \
+        If you're stuck, make sure you're using the full input data.</p></article></main></html>";
+    const CORRECT: &str =
+        "<main><article><p>That's the right answer!  You are <em>one gold star</em> closer.</p></article></main>";
+
+    fn guess(answer: &str, verdict: Verdict) -> Guess {
+        Guess {
+            day: 6,
+            part: 2,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn parse_response_test() {
+        assert_eq!(parse_response(TOO_LOW), Verdict::TooLow);
+        assert_eq!(parse_response(CORRECT), Verdict::Correct);
+        assert_eq!(
+            parse_response(
+                "<article><p>That's not the right answer; your answer is too high.</p></article>"
+            ),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_response(
+                "<article><p>That's not the right answer.  If you're stuck...</p></article>"
+            ),
+            Verdict::Wrong
+        );
+        assert_eq!(
+            parse_response("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.</p></article>"),
+            Verdict::Wait("You have 34s".to_string())
+        );
+        assert_eq!(
+            parse_response("<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(
+            parse_response("<p>Teapot</p>"),
+            Verdict::Unrecognised("Teapot".to_string())
+        );
+    }
+
+    #[test]
+    fn refusal_test() {
+        let guesses = vec![guess("1792", Verdict::TooLow)];
+        assert_eq!(
+            refusal(&guesses, 6, 2, "1792"),
+            Some("1792 was already submitted for day 6 part 2 and was too low".to_string())
+        );
+        assert_eq!(refusal(&guesses, 6, 2, "1933"), None);
+        assert_eq!(refusal(&guesses, 6, 1, "1792"), None);
+
+        let guesses = vec![guess("1933", Verdict::Correct)];
+        assert!(refusal(&guesses, 6, 2, "1934").is_some());
+    }
+
+    #[test]
+    fn submit_test() {
+        let path = &temp_path("submit_test.txt");
+        let _ = fs::remove_file(path);
+        let (base_url, server) = stub_server(vec![(200, TOO_LOW), (200, CORRECT)]);
+        let config = stub_config(&base_url);
+
+        assert_eq!(submit(&config, path, 6, 2, "1792"), Ok(Verdict::TooLow));
+        // refused without asking the server, which only has two responses
+        assert!(submit(&config, path, 6, 2, "1792")
+            .unwrap_err()
+            .contains("was too low"));
+        assert_eq!(submit(&config, path, 6, 2, "1933"), Ok(Verdict::Correct));
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2024/day/6/answer HTTP/1.1"));
+        assert!(requests[0].ends_with("level=2&answer=1792"));
+        assert_eq!(
+            load(path),
+            Ok(vec![
+                guess("1792", Verdict::TooLow),
+                guess("1933", Verdict::Correct)
+            ])
+        );
+        fs::remove_file(path).unwrap();
+    }
+}
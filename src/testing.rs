@@ -0,0 +1,10 @@
+//! Helpers shared by the library's and the binary's tests; not part of the API.
+
+use std::env;
+
+/// A path in the system temp directory that no other test run will use, e.g. for
+/// `temp_path("answers_record_test.txt")`. Nothing is created there.
+pub fn temp_path(name: &str) -> String {
+    let path = env::temp_dir().join(format!("{}_{}", std::process::id(), name));
+    path.to_str().unwrap().to_string()
+}
@@ -1,56 +1,218 @@
+//! Helpers shared by the days: reading and parsing inputs, with errors that point at the
+//! offending line, and a few small collection and arithmetic utilities.
+
 use core::str;
 use std::cmp::Eq;
 use std::collections::HashMap;
-use std::fmt::Debug;
-use std::fs::{self, File};
+use std::fmt::{self, Display};
+use std::fs;
 use std::hash::Hash;
-use std::io::{BufRead, BufReader};
-use std::path::Path;
+use std::io::{self, BufRead};
 use std::str::FromStr;
 
 pub mod matrix;
 pub mod plane;
 
-pub fn string_iter(path: &str) -> impl Iterator<Item = String> {
-    let path = Path::new(path);
-    let file = File::open(&path).expect(format!("couldn't open {}", path.display()).as_str());
-    BufReader::new(file).lines().map(Result::unwrap)
+/// An input that couldn't be read or understood.
+///
+/// `line` and `column` are 1-based, and both are 0 when the problem is with the whole input
+/// (e.g. the file doesn't exist).
+#[derive(PartialEq, Debug, Clone)]
+pub struct ParseError {
+    pub path: String,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
 }
 
-pub fn read_all(path: &str) -> String {
-    fs::read_to_string(path).expect(format!("Could not read file {}", path).as_str())
+impl ParseError {
+    /// An error about the input as a whole rather than one of its lines.
+    pub fn whole_file(path: &str, message: impl Into<String>) -> ParseError {
+        ParseError {
+            path: path.to_string(),
+            line: 0,
+            column: 0,
+            text: String::new(),
+            message: message.into(),
+        }
+    }
 }
 
-fn to_spaced_int_vec<T: FromStr + Debug>(s: String) -> Vec<T>
-where
-    <T as FromStr>::Err: Debug,
-{
-    if s.is_empty() {
-        vec![]
-    } else {
-        s.split_whitespace()
-            .map(str::parse)
-            .map(|s| s.expect("integer expected"))
-            .collect()
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.line == 0 {
+            write!(f, "{}: {}", self.path, self.message)
+        } else {
+            write!(
+                f,
+                "{}:{}:{}: {}: '{}'",
+                self.path, self.line, self.column, self.message, self.text
+            )
+        }
     }
 }
 
-pub fn as_spaced_int_vec<T: FromStr + Debug>(path: &str) -> impl Iterator<Item = Vec<T>>
-where
-    <T as FromStr>::Err: Debug,
-{
-    string_iter(path).map(to_spaced_int_vec)
+/// One line of an input, kept with where it came from so that errors can point at it.
+#[derive(Clone, Copy)]
+pub struct Line<'a> {
+    pub path: &'a str,
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    pub fn new(path: &'a str, number: usize, text: &'a str) -> Line<'a> {
+        Line { path, number, text }
+    }
+
+    /// An error about part of this line. `part` must be a slice of the line's text, or empty to
+    /// mean the end of the line.
+    pub fn error(&self, part: &str, message: impl Into<String>) -> ParseError {
+        let start = self.text.as_ptr() as usize;
+        let offset = (part.as_ptr() as usize)
+            .checked_sub(start)
+            .filter(|&offset| offset <= self.text.len() && !part.is_empty())
+            .unwrap_or(self.text.len());
+        ParseError {
+            path: self.path.to_string(),
+            line: self.number,
+            column: self.text[..offset].chars().count() + 1,
+            text: if part.is_empty() {
+                self.text.to_string()
+            } else {
+                part.to_string()
+            },
+            message: message.into(),
+        }
+    }
+
+    /// Parse part of the line, e.g. one of its fields, as the kind of value described by `what`.
+    pub fn parse<T: FromStr>(&self, part: &str, what: &str) -> Result<T, ParseError> {
+        part.trim()
+            .parse()
+            .map_err(|_| self.error(part, format!("expected {}", what)))
+    }
 }
 
-pub fn as_int_pairs(path: &str) -> impl Iterator<Item = (u32, u32)> {
-    string_iter(path).map(to_spaced_int_vec).map(|v| {
-        if v.len() != 2 {
-            panic!("Expected 2 integers per line, got {}", v.len());
+/// The path that means standard input, as in most command line tools.
+pub const STDIN: &str = "-";
+
+/// The text of an input along with the name errors should refer to it by, usually its path.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Source {
+    pub name: String,
+    pub text: String,
+}
+
+impl Source {
+    /// An input held in memory, e.g. an example embedded in a test.
+    pub fn new(name: &str, text: &str) -> Source {
+        Source {
+            name: name.to_string(),
+            text: text.to_string(),
+        }
+    }
+
+    /// Read a file, or standard input if the path is [`STDIN`].
+    pub fn read(path: &str) -> Result<Source, ParseError> {
+        if path == STDIN {
+            return Source::from_reader("<stdin>", io::stdin().lock());
         }
-        (v[0], v[1])
-    })
+        fs::read_to_string(path)
+            .map(|text| Source {
+                name: path.to_string(),
+                text,
+            })
+            .map_err(|e| ParseError::whole_file(path, format!("could not read: {}", e)))
+    }
+
+    /// Everything left in the reader, e.g. a locked stdin or a byte slice.
+    pub fn from_reader(name: &str, mut reader: impl BufRead) -> Result<Source, ParseError> {
+        let mut text = String::new();
+        reader
+            .read_to_string(&mut text)
+            .map_err(|e| ParseError::whole_file(name, format!("could not read: {}", e)))?;
+        Ok(Source {
+            name: name.to_string(),
+            text,
+        })
+    }
+
+    /// Each line with its 1-based number, for parsers that report errors.
+    pub fn lines(&self) -> impl Iterator<Item = Line<'_>> {
+        self.text
+            .lines()
+            .enumerate()
+            .map(|(idx, text)| Line::new(&self.name, idx + 1, text))
+    }
+
+    /// An error about the input as a whole, e.g. something missing from the end.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::whole_file(&self.name, message)
+    }
+}
+
+/// The whole of a file.
+pub fn read_all(path: &str) -> Result<String, ParseError> {
+    fs::read_to_string(path)
+        .map_err(|e| ParseError::whole_file(path, format!("could not read: {}", e)))
+}
+
+/// Each line of a file, without its line ending.
+pub fn string_iter(path: &str) -> Result<impl Iterator<Item = String>, ParseError> {
+    let lines: Vec<String> = string_iter_from_str(&read_all(path)?).collect();
+    Ok(lines.into_iter())
+}
+
+/// Each line of the text, without its line ending; [`string_iter`] for input already in memory.
+pub fn string_iter_from_str(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.lines().map(str::to_string)
 }
 
+fn to_spaced_int_vec<T: FromStr>(line: &Line) -> Result<Vec<T>, ParseError> {
+    line.text
+        .split_whitespace()
+        .map(|field| line.parse(field, "an integer"))
+        .collect()
+}
+
+/// One `Vec` per line of a file of whitespace-separated integers.
+pub fn as_spaced_int_vec<T: FromStr>(path: &str) -> Result<Vec<Vec<T>>, ParseError> {
+    as_spaced_int_vec_from_source(&Source::read(path)?)
+}
+
+/// [`as_spaced_int_vec`] for input already read.
+pub fn as_spaced_int_vec_from_source<T: FromStr>(
+    source: &Source,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    source
+        .lines()
+        .map(|line| to_spaced_int_vec(&line))
+        .collect()
+}
+
+/// A file of lines of exactly two whitespace-separated integers.
+pub fn as_int_pairs(path: &str) -> Result<Vec<(u32, u32)>, ParseError> {
+    as_int_pairs_from_source(&Source::read(path)?)
+}
+
+/// [`as_int_pairs`] for input already read.
+pub fn as_int_pairs_from_source(source: &Source) -> Result<Vec<(u32, u32)>, ParseError> {
+    source
+        .lines()
+        .map(|line| {
+            let fields: Vec<&str> = line.text.split_whitespace().collect();
+            match fields[..] {
+                [a, b] => Ok((line.parse(a, "an integer")?, line.parse(b, "an integer")?)),
+                [_, _, extra, ..] => Err(line.error(extra, "expected 2 integers per line")),
+                _ => Err(line.error("", "expected 2 integers per line")),
+            }
+        })
+        .collect()
+}
+
+/// How many times each value occurs.
 pub fn freqs_u32<T: Hash + Eq>(v: Vec<T>) -> HashMap<T, u32> {
     let mut freqs = HashMap::new();
     for x in v {
@@ -59,6 +221,7 @@ pub fn freqs_u32<T: Hash + Eq>(v: Vec<T>) -> HashMap<T, u32> {
     freqs
 }
 
+/// How many times each value occurs, for counts that may not fit in a `u32`.
 pub fn freqs_u64<T: Hash + Eq>(v: Vec<T>) -> HashMap<T, u64> {
     let mut freqs = HashMap::new();
     for x in v {
@@ -67,6 +230,7 @@ pub fn freqs_u64<T: Hash + Eq>(v: Vec<T>) -> HashMap<T, u64> {
     freqs
 }
 
+/// Every copy of `v` with exactly one element removed, in order of the removed index.
 pub fn subsets_removing_1<T: Clone>(v: &Vec<T>) -> Vec<Vec<T>> {
     let mut result = Vec::new();
     for i in 0..v.len() {
@@ -77,6 +241,7 @@ pub fn subsets_removing_1<T: Clone>(v: &Vec<T>) -> Vec<Vec<T>> {
     result
 }
 
+/// Every unordered pair of elements, each element paired only with those after it.
 pub fn all_pairs<T: Clone>(v: &Vec<T>) -> Vec<(T, T)> {
     let mut result = Vec::new();
     for i in 0..v.len() {
@@ -87,6 +252,7 @@ pub fn all_pairs<T: Clone>(v: &Vec<T>) -> Vec<(T, T)> {
     result
 }
 
+/// The distinct values, in no particular order.
 pub fn distinct<T>(v: Vec<T>) -> Vec<T>
 where
     T: Eq + Hash + Clone,
@@ -100,18 +266,30 @@ where
     seen.keys().cloned().collect()
 }
 
+/// The values in ascending order.
+pub fn sorted<T: Ord>(mut v: Vec<T>) -> Vec<T> {
+    v.sort();
+    v
+}
+
+/// Whether two strings have the same characters.
 pub fn equals(a: &str, b: &str) -> bool {
     a.chars().count() == b.chars().count() && a.chars().zip(b.chars()).all(|(a, b)| a == b)
 }
 
+/// The number of decimal digits in `n`, which must be positive.
 pub fn digit_count(n: u64) -> u32 {
     n.ilog10() + 1
 }
 
-// solves a pair of simultaneous equations of the form:
-// a1 * x + b1 * y = c1
-// a2 * x + b2 * y = c2
-// returns None if there is no solution (parallel lines) or if there is no positive integer solution
+/// Solves a pair of simultaneous equations of the form:
+///
+/// ```text
+/// a1 * x + b1 * y = c1
+/// a2 * x + b2 * y = c2
+/// ```
+///
+/// Returns None if there is no solution (parallel lines) or if there is no positive integer solution.
 pub fn simultaneous_equations_posint_result(
     a1: &i64,
     b1: &i64,
@@ -143,14 +321,82 @@ pub fn simultaneous_equations_posint_result(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::temp_path;
+
+    #[test]
+    fn line_error_test() {
+        let line = Line::new("inputs/d5.txt", 3, "47|5x3");
+        let (_, b) = line.text.split_once('|').unwrap();
+        let err = line.parse::<u32>(b, "an integer").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError {
+                path: "inputs/d5.txt".to_string(),
+                line: 3,
+                column: 4,
+                text: "5x3".to_string(),
+                message: "expected an integer".to_string()
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "inputs/d5.txt:3:4: expected an integer: '5x3'"
+        );
+
+        // an empty part points past the end of the line and shows all of it
+        let err = line.error("", "expected more");
+        assert_eq!((err.column, err.text.as_str()), (7, "47|5x3"));
+    }
+
+    #[test]
+    fn as_int_pairs_test() {
+        let pairs = |text| as_int_pairs_from_source(&Source::new("d1.txt", text));
+        assert_eq!(pairs("3   4\n4   3\n"), Ok(vec![(3, 4), (4, 3)]));
+
+        let err = pairs("3   4\n4   x3\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "x3"));
+
+        let err = pairs("3   4   5\n").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 9, "5"));
+    }
+
+    #[test]
+    fn source_test() {
+        let source = Source::from_reader("piped", "1 2\n3 4\n".as_bytes()).unwrap();
+        assert_eq!(source, Source::new("piped", "1 2\n3 4\n"));
+        assert_eq!(
+            as_spaced_int_vec_from_source(&source),
+            Ok(vec![vec![1, 2], vec![3, 4]])
+        );
+
+        let path = &temp_path("utils_source_test.txt");
+        fs::write(path, "1 2\n3 4\n").unwrap();
+        assert_eq!(Source::read(path).unwrap().text, source.text);
+        assert_eq!(&Source::read(path).unwrap().name, path);
+        assert_eq!(as_spaced_int_vec(path), Ok(vec![vec![1, 2], vec![3, 4]]));
+        assert_eq!(as_int_pairs(path), Ok(vec![(1, 2), (3, 4)]));
+
+        assert_eq!(
+            string_iter(path).unwrap().collect::<Vec<String>>(),
+            vec!["1 2", "3 4"]
+        );
+
+        fs::remove_file(path).unwrap();
+        assert!(string_iter(path).is_err());
+        let err = Source::read(path).unwrap_err();
+        assert_eq!((err.line, err.column), (0, 0));
+        assert!(err
+            .to_string()
+            .starts_with(&format!("{}: could not read", path)));
+    }
 
     #[test]
     fn all_pairs_test() {
         assert_eq!(all_pairs(&vec!['a']), vec![]);
         assert_eq!(all_pairs(&vec!['a', 'b']), vec![('a', 'b')]);
         assert_eq!(
-            all_pairs(&vec!['a', 'b', 'c']).sort(),
-            vec![('a', 'b'), ('b', 'c'), ('a', 'c')].sort()
+            sorted(all_pairs(&vec!['a', 'b', 'c'])),
+            sorted(vec![('a', 'b'), ('b', 'c'), ('a', 'c')])
         );
     }
 
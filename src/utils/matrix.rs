@@ -1,13 +1,25 @@
+//! Grids stored as `Vec<Vec<T>>`, indexed by row and then column.
+
 use std::fmt::Display;
 
+use super::ParseError;
+
+/// The characters of a line.
 pub fn to_char_vec(s: String) -> Vec<char> {
     s.chars().collect()
 }
 
-pub fn as_char_matrix(path: &str) -> Vec<Vec<char>> {
-    super::string_iter(path).map(to_char_vec).collect()
+/// A grid of the characters in a file, one row per line.
+pub fn as_char_matrix(path: &str) -> Result<Vec<Vec<char>>, ParseError> {
+    Ok(as_char_matrix_from_str(&super::read_all(path)?))
+}
+
+/// A grid of the characters in the text, one row per line.
+pub fn as_char_matrix_from_str(text: &str) -> Vec<Vec<char>> {
+    super::string_iter_from_str(text).map(to_char_vec).collect()
 }
 
+/// The digits of a line, with `bad_digit_flag` standing in for any character that isn't one.
 pub fn to_digit_vec(s: &String, bad_digit_flag: u32) -> Vec<u32> {
     if s.is_empty() {
         vec![]
@@ -19,12 +31,22 @@ pub fn to_digit_vec(s: &String, bad_digit_flag: u32) -> Vec<u32> {
     }
 }
 
-pub fn as_digit_matrix(path: &str, bad_digit_flag: u32) -> Vec<Vec<u32>> {
-    super::string_iter(path)
+/// A grid of the digits in a file, one row per line; see [`to_digit_vec`].
+pub fn as_digit_matrix(path: &str, bad_digit_flag: u32) -> Result<Vec<Vec<u32>>, ParseError> {
+    Ok(as_digit_matrix_from_str(
+        &super::read_all(path)?,
+        bad_digit_flag,
+    ))
+}
+
+/// A grid of the digits in the text, one row per line; see [`to_digit_vec`].
+pub fn as_digit_matrix_from_str(text: &str, bad_digit_flag: u32) -> Vec<Vec<u32>> {
+    super::string_iter_from_str(text)
         .map(|s| to_digit_vec(&s, bad_digit_flag))
         .collect()
 }
 
+/// The transpose of a rectangular grid, so that rows become columns.
 pub fn flip_matrix<T: Copy>(mat: &Vec<Vec<T>>) -> Vec<Vec<T>> {
     let height = mat.len();
     let width = mat[0].len();
@@ -39,7 +61,7 @@ pub fn flip_matrix<T: Copy>(mat: &Vec<Vec<T>>) -> Vec<Vec<T>> {
     flipped
 }
 
-// return NxM new matrices, with the same values as the original except a single value is replaced with a new value
+/// One copy of the grid for each cell, with that cell replaced by `new_val`.
 pub fn replace_one_cell<T: Copy>(mat: &Vec<Vec<T>>, new_val: T) -> Vec<Vec<Vec<T>>> {
     let mut result = Vec::new();
     for (row_idx, row) in mat.iter().enumerate() {
@@ -52,6 +74,7 @@ pub fn replace_one_cell<T: Copy>(mat: &Vec<Vec<T>>, new_val: T) -> Vec<Vec<Vec<T
     result
 }
 
+/// Print the grid to stdout, one row per line.
 pub fn pretty_print<T: Display>(mat: &Vec<Vec<T>>) {
     for row in mat {
         for cell in row {
@@ -61,6 +84,7 @@ pub fn pretty_print<T: Display>(mat: &Vec<Vec<T>>) {
     }
 }
 
+/// The coordinates with duplicates removed, keeping the first of each.
 pub fn unique_coordinates(mat: &Vec<(usize, usize)>) -> Vec<(usize, usize)> {
     let mut unique = vec![];
     for coord in mat {
@@ -71,14 +95,17 @@ pub fn unique_coordinates(mat: &Vec<(usize, usize)>) -> Vec<(usize, usize)> {
     unique
 }
 
+/// Whether `(row, col)` is inside a grid of the given size.
 pub fn in_bounds(row_count: usize, col_count: usize, row: isize, col: isize) -> bool {
     row >= 0 && row < row_count as isize && col >= 0 && col < col_count as isize
 }
 
+/// Whether `(row, col)` is inside the grid.
 pub fn in_bounds_m<T>(mat: &Vec<Vec<T>>, row: isize, col: isize) -> bool {
     in_bounds(mat.len(), mat[0].len(), row, col)
 }
 
+/// The `(row, col)` of every cell equal to `target`, in reading order.
 pub fn find_all<T: PartialEq>(mat: &Vec<Vec<T>>, target: T) -> Vec<(usize, usize)> {
     let mut result = Vec::new();
     for (row_idx, row) in mat.iter().enumerate() {
@@ -91,7 +118,7 @@ pub fn find_all<T: PartialEq>(mat: &Vec<Vec<T>>, target: T) -> Vec<(usize, usize
     result
 }
 
-// returns (row_count, col_count)
+/// The size of a rectangular grid as `(row_count, col_count)`.
 pub fn dimensions<T: PartialEq>(mat: &Vec<Vec<T>>) -> (usize, usize) {
     (mat.len(), mat[0].len())
 }
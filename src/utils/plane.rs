@@ -1,6 +1,10 @@
-// handles 2D movement in a plane:
-// row 0 is on the top and 0,0 is top left
+//! Movement around a 2D plane of characters.
+//!
+//! Row 0 is at the top, so (0, 0) is the top left corner.
 
+use super::{ParseError, Source};
+
+/// One of the four ways to move between neighbouring cells.
 #[derive(PartialEq, Eq, Debug, Hash, Clone, Copy)]
 pub enum Direction {
     Up,
@@ -10,6 +14,7 @@ pub enum Direction {
 }
 
 impl Direction {
+    /// The direction for one of the arrows `^`, `v`, `<` or `>`.
     pub fn from_char(c: char) -> Self {
         match c {
             '^' => Direction::Up,
@@ -20,6 +25,7 @@ impl Direction {
         }
     }
 
+    /// Every direction, clockwise from up.
     pub fn all() -> Vec<Direction> {
         vec![
             Direction::Up,
@@ -30,6 +36,7 @@ impl Direction {
     }
 }
 
+/// Something at a position in the plane and heading in some direction.
 #[derive(PartialEq, Eq, Debug, Hash, Clone)]
 pub struct MovingObject {
     pub row: usize,
@@ -38,7 +45,7 @@ pub struct MovingObject {
     pub out_of_bounds: bool,
 }
 
-// result is dest_row, dest_col, Some(direction if out of bounds)
+/// Where one step in `dir` leads. A step off the edge stays put and sets `out_of_bounds`.
 pub fn move_one(
     row: usize,
     col: usize,
@@ -48,9 +55,9 @@ pub fn move_one(
 ) -> MovingObject {
     let (out_of_bounds, new_row, new_col) = match (dir, row, col) {
         // out of bounds
-        (Direction::Up, r, _) if r == 0 => (true, row, col),
+        (Direction::Up, 0, _) => (true, row, col),
         (Direction::Down, r, _) if r == row_count - 1 => (true, row, col),
-        (Direction::Left, _, c) if c == 0 => (true, row, col),
+        (Direction::Left, _, 0) => (true, row, col),
         (Direction::Right, _, c) if c == col_count - 1 => (true, row, col),
         // normal movement
         (Direction::Up, _, _) => (false, row - 1, col),
@@ -67,6 +74,7 @@ pub fn move_one(
     }
 }
 
+/// The obstacle directly in front of `me`, if there is one.
 pub fn obstacle_ahead(
     plane: &Vec<Vec<char>>,
     obstacles: &Vec<char>,
@@ -88,6 +96,7 @@ pub fn obstacle_ahead(
     }
 }
 
+/// The direction after a quarter turn clockwise.
 pub fn turn_right_90_degrees(dir: Direction) -> Direction {
     match dir {
         Direction::Up => Direction::Right,
@@ -97,6 +106,7 @@ pub fn turn_right_90_degrees(dir: Direction) -> Direction {
     }
 }
 
+/// The direction after a quarter turn anticlockwise.
 pub fn turn_left_90_degrees(dir: Direction) -> Direction {
     match dir {
         Direction::Up => Direction::Left,
@@ -111,7 +121,7 @@ fn move_forward_or_turn_right(
     obstacles: &Vec<char>,
     start: &MovingObject,
 ) -> MovingObject {
-    if obstacle_ahead(plane, &obstacles, &start).is_some() {
+    if obstacle_ahead(plane, obstacles, start).is_some() {
         let new_dir = turn_right_90_degrees(start.dir);
         move_forward_or_turn_right(
             plane,
@@ -128,8 +138,8 @@ fn move_forward_or_turn_right(
     }
 }
 
-// generate the path to the exit, turning right when an obstacle is hit
-// return None if a loop is detected
+/// The path until stepping off the plane, turning right whenever an obstacle is in the way.
+/// None if the path loops forever instead.
 pub fn path_to_exit_turning_right(
     plane: &Vec<Vec<char>>,
     obstacles: &Vec<char>,
@@ -154,6 +164,7 @@ pub fn path_to_exit_turning_right(
     Some(path)
 }
 
+/// The positions visited on a path, without repeats.
 pub fn unique_spaces(path: &Vec<MovingObject>) -> Vec<(usize, usize)> {
     let mut unique = vec![];
     for mo in path {
@@ -164,8 +175,9 @@ pub fn unique_spaces(path: &Vec<MovingObject>) -> Vec<(usize, usize)> {
     unique
 }
 
-// find some character in the plane
-// expectation: only one of this character exists
+/// The position of a character that appears exactly once, such as a start marker.
+///
+/// Panics if the character isn't there, so parsers should [`check_unique_element`] first.
 pub fn find_unique_element(plane: &Vec<Vec<char>>, to_find: char) -> (usize, usize) {
     for (row, row_vec) in plane.iter().enumerate() {
         for (col, char_at_col) in row_vec.iter().enumerate() {
@@ -177,6 +189,21 @@ pub fn find_unique_element(plane: &Vec<Vec<char>>, to_find: char) -> (usize, usi
     panic!("{} not found", to_find);
 }
 
+/// An error about the input unless the character, called `name` in the message, appears exactly
+/// once in the plane.
+pub fn check_unique_element(
+    input: &Source,
+    plane: &Vec<Vec<char>>,
+    to_find: char,
+    name: &str,
+) -> Result<(), ParseError> {
+    if find_all_elements(plane, to_find).len() != 1 {
+        return Err(input.error(format!("expected exactly one {} '{}'", name, to_find)));
+    }
+    Ok(())
+}
+
+/// The `(row, col)` of every occurrence of the character, in reading order.
 pub fn find_all_elements(plane: &Vec<Vec<char>>, to_find: char) -> Vec<(usize, usize)> {
     let mut result = vec![];
     for (row, row_vec) in plane.iter().enumerate() {
@@ -508,6 +535,21 @@ mod tests {
         assert_eq!(unique_spaces(&path), expected);
     }
 
+    #[test]
+    fn check_unique_element_test() {
+        let input = Source::new("maze", "S.\n.S\n");
+        let plane = vec![vec!['S', '.'], vec!['.', 'S']];
+        assert!(check_unique_element(&input, &plane, '.', "floor").is_err());
+        assert_eq!(
+            check_unique_element(&input, &plane, 'E', "end")
+                .unwrap_err()
+                .to_string(),
+            "maze: expected exactly one end 'E'"
+        );
+        let plane = vec![vec!['S', '.']];
+        assert_eq!(check_unique_element(&input, &plane, 'S', "start"), Ok(()));
+    }
+
     #[test]
     fn path_right_loop_test() {
         let obstacles = vec!['x'];